@@ -1,8 +1,11 @@
 use criterion::{criterion_group, criterion_main, Criterion};
-use libdtf::{core::diff_types::{
-    ArrayDiff, Checker, Config, KeyDiff, TypeDiff, ValueDiff, WorkingContext,
-    WorkingFile,
-}, json::diff_types::CheckingData};
+use libdtf::{
+    core::diff_types::{
+        ArrayDiff, Checker, Config, DiffKinds, KeyDiff, TypeDiff, ValueDiff, WorkingContext,
+        WorkingFile,
+    },
+    json::{self, diff_types::CheckingData},
+};
 use serde_json::json;
 
 const FILE_NAME_A: &str = "a.json";
@@ -34,8 +37,8 @@ fn benchmark_find_key_diffs(c: &mut Criterion) {
         bencher.iter(|| {
             let mut key_checker: CheckingData<KeyDiff> = CheckingData::new(
                 "",
-                &a.as_object().unwrap(),
-                &b.as_object().unwrap(),
+                a.as_object().unwrap(),
+                b.as_object().unwrap(),
                 &working_context,
             );
             key_checker.check();
@@ -101,8 +104,8 @@ fn benchmark_find_type_diffs_no_array_same_order(c: &mut Criterion) {
         bencher.iter(|| {
             let mut type_checker: CheckingData<TypeDiff> = CheckingData::new(
                 "",
-                &a.as_object().unwrap(),
-                &b.as_object().unwrap(),
+                a.as_object().unwrap(),
+                b.as_object().unwrap(),
                 &working_context,
             );
             type_checker.check();
@@ -168,8 +171,8 @@ fn benchmark_find_type_diffs_array_same_order(c: &mut Criterion) {
         bencher.iter(|| {
             let mut type_checker: CheckingData<TypeDiff> = CheckingData::new(
                 "",
-                &a.as_object().unwrap(),
-                &b.as_object().unwrap(),
+                a.as_object().unwrap(),
+                b.as_object().unwrap(),
                 &working_context,
             );
             type_checker.check();
@@ -244,8 +247,8 @@ fn benchmark_find_value_diffs_no_array_same_order(c: &mut Criterion) {
         bencher.iter(|| {
             let mut value_checker: CheckingData<ValueDiff> = CheckingData::new(
                 "",
-                &a.as_object().unwrap(),
-                &b.as_object().unwrap(),
+                a.as_object().unwrap(),
+                b.as_object().unwrap(),
                 &working_context,
             );
             value_checker.check();
@@ -320,8 +323,8 @@ fn benchmark_find_value_diffs_array_same_order(c: &mut Criterion) {
         bencher.iter(|| {
             let mut value_checker: CheckingData<ValueDiff> = CheckingData::new(
                 "",
-                &a.as_object().unwrap(),
-                &b.as_object().unwrap(),
+                a.as_object().unwrap(),
+                b.as_object().unwrap(),
                 &working_context,
             );
             value_checker.check();
@@ -372,8 +375,8 @@ fn benchmark_find_array_diffs(c: &mut Criterion) {
         bencher.iter(|| {
             let mut array_checker: CheckingData<ArrayDiff> = CheckingData::new(
                 "",
-                &a.as_object().unwrap(),
-                &b.as_object().unwrap(),
+                a.as_object().unwrap(),
+                b.as_object().unwrap(),
                 &working_context,
             );
             array_checker.check();
@@ -381,6 +384,55 @@ fn benchmark_find_array_diffs(c: &mut Criterion) {
     });
 }
 
+fn benchmark_compare(c: &mut Criterion) {
+    // arrange
+    let a = json!({
+        "a_has": "a_has",
+        "diff_string": "a",
+        "a_string_b_int": "a_string_b_int",
+        "diff_array": [
+            1, 2, 3, 4
+        ],
+        "nested": {
+            "a_has": "a_has",
+            "diff_boolean": true,
+            "diff_array": [
+                1, 2, 3, 4
+            ],
+        },
+    });
+
+    let b = json!({
+        "b_has": "b_has",
+        "diff_string": "b",
+        "a_string_b_int": 2,
+        "diff_array": [
+            1, 2, 8, 4
+        ],
+        "nested": {
+            "b_has": "b_has",
+            "diff_boolean": false,
+            "diff_array": [
+                1, 2, 8, 4
+            ],
+        },
+    });
+
+    let working_context = create_test_working_context(false);
+
+    // act
+    c.bench_function("Compare All Diffs", |bencher| {
+        bencher.iter(|| {
+            json::compare(
+                a.as_object().unwrap(),
+                b.as_object().unwrap(),
+                &working_context,
+                DiffKinds::all(),
+            );
+        })
+    });
+}
+
 // Benchmark utils
 
 fn create_test_working_context(array_same_order: bool) -> WorkingContext {
@@ -397,6 +449,7 @@ criterion_group!(
     benchmark_find_type_diffs_array_same_order,
     benchmark_find_value_diffs_no_array_same_order,
     benchmark_find_value_diffs_array_same_order,
-    benchmark_find_array_diffs
+    benchmark_find_array_diffs,
+    benchmark_compare
 );
 criterion_main!(benches);
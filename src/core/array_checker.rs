@@ -0,0 +1,652 @@
+/// Collects differences between the arrays of 2 data sets.
+/// Stores `ArrayDiff` values
+///
+/// 1. First we check if the user has specified the option that states, that arrays should be in the same order. If the option is turned on, we don't do anything. The array will be checked for value differences instead.
+/// 2. We iterate through object `a` and if a field is present in `b` as well, only then do we take action
+///     1. We construct a new path by adding the currently checked fields key to the path in our checker object. That's how we handle the keys of nested objects. If the config skips the path, we move on to the next field.
+///     2. If `a` and `b` are both objects we recursively start the process over for the nested objects.
+///     3. If both fields are arrays, we collect the differences:
+///         * `AHas` and `BMisses` type of `ArrayDiffDesc` vectors, for values, that are present in `a` but not in `b`
+///         * `BHas` and `AMisses` type of `ArrayDiffDesc` vectors, for values, that are present in `b` but not in `a`
+///     4. We iterate through all the collected vectors and create `ArrayDiff` objects for each of them, which we store in our `diffs` vector
+use std::collections::HashMap;
+
+use crate::core::{
+    diff_types::{ArrayDiff, ArrayDiffDesc, Checker, CheckingData, Config, DiffCollection},
+    node::{fingerprint_items, Node, NodeObject},
+    path::Path,
+    record,
+};
+
+impl<'a, A: Node, B: Node> Checker<ArrayDiff> for CheckingData<'a, ArrayDiff, A, B> {
+    fn check(&mut self) {
+        if !self.working_context.config.array_same_order {
+            for (a_key, a_value) in self.a.entries() {
+                let key = self.key.with_key(&a_key);
+                if self.working_context.config.skips(&key) {
+                    continue;
+                }
+
+                if let Some(b_value) = self.b.get(&a_key) {
+                    self.find_array_diffs_in_values(&key, a_value, b_value);
+                }
+            }
+        }
+    }
+
+    fn check_and_get(&mut self) -> &DiffCollection<ArrayDiff> {
+        self.check();
+        &self.diffs
+    }
+
+    fn diffs(&self) -> &Vec<ArrayDiff> {
+        self.diffs.diffs()
+    }
+}
+
+impl<'a, A: Node, B: Node> CheckingData<'a, ArrayDiff, A, B> {
+    fn find_array_diffs_in_values(&mut self, key_in: &Path, a: &A, b: &B) {
+        if let (Some(a_object), Some(b_object)) = (a.as_object(), b.as_object()) {
+            self.find_array_diffs_in_objects(key_in, a_object, b_object);
+        }
+
+        if let (Some(a_array), Some(b_array)) = (a.as_array(), b.as_array()) {
+            self.diffs.append(&mut find_array_diffs_in_arrays(
+                key_in,
+                a_array,
+                b_array,
+                &self.working_context.config,
+            ));
+        }
+    }
+
+    fn find_array_diffs_in_objects(&mut self, key_in: &Path, a: &A::Object, b: &B::Object) {
+        let mut array_checker: CheckingData<ArrayDiff, A, B> =
+            CheckingData::new(key_in.clone(), a, b, self.working_context);
+
+        array_checker.check();
+        self.diffs.concatenate(&mut array_checker.diffs);
+    }
+}
+
+/// Collects the items present in one of the arrays, but missing from the other as `ArrayDiff` values
+pub(crate) fn find_array_diffs_in_arrays<A: Node, B: Node>(
+    key_in: &Path,
+    a: &[A],
+    b: &[B],
+    config: &Config,
+) -> Vec<ArrayDiff> {
+    let ocurrence_counts_a = count_items(key_in, a, config);
+    let ocurrence_counts_b = count_items(key_in, b, config);
+
+    let a_has = calculate_difference(&ocurrence_counts_a, &ocurrence_counts_b);
+    let b_has = calculate_difference(&ocurrence_counts_b, &ocurrence_counts_a);
+
+    let a_diffs = a_has.iter().map(|item| (ArrayDiffDesc::AHas, *item));
+    let b_diffs = b_has.iter().map(|item| (ArrayDiffDesc::BHas, *item));
+    let a_misses = b_has.iter().map(|item| (ArrayDiffDesc::AMisses, *item));
+    let b_misses = a_has.iter().map(|item| (ArrayDiffDesc::BMisses, *item));
+
+    a_diffs
+        .map(|(desc, item)| create_array_diff(key_in, desc, item, config))
+        .chain(a_misses.map(|(desc, item)| create_array_diff(key_in, desc, item, config)))
+        .chain(b_diffs.map(|(desc, item)| create_array_diff(key_in, desc, item, config)))
+        .chain(b_misses.map(|(desc, item)| create_array_diff(key_in, desc, item, config)))
+        .collect()
+}
+
+fn create_array_diff<N: Node>(
+    key_in: &Path,
+    descriptor: ArrayDiffDesc,
+    item: &N,
+    config: &Config,
+) -> ArrayDiff {
+    ArrayDiff::new(key_in.clone(), descriptor, item.to_display_string())
+        .with_json(record(config, item))
+}
+
+/// Counts the items by their fingerprints and keeps the first occurrence of each
+fn count_items<'n, N: Node>(
+    key_in: &Path,
+    items: &'n [N],
+    config: &Config,
+) -> HashMap<String, (&'n N, i32)> {
+    let mut occurrence_counts = HashMap::new();
+
+    for (item, fingerprint) in fingerprint_items(items, key_in, config) {
+        occurrence_counts.entry(fingerprint).or_insert((item, 0)).1 += 1;
+    }
+
+    occurrence_counts
+}
+
+fn calculate_difference<'n, A: Node, B: Node>(
+    ocurrence_counts_a: &HashMap<String, (&'n A, i32)>,
+    ocurrence_counts_b: &HashMap<String, (&B, i32)>,
+) -> Vec<&'n A> {
+    let mut difference = vec![];
+
+    for (key, (item, count)) in ocurrence_counts_a.iter() {
+        let count_b = ocurrence_counts_b.get(key).map_or(0, |(_, count)| *count);
+        let diff = count - count_b;
+
+        for _ in 0..diff {
+            difference.push(*item);
+        }
+    }
+
+    difference
+}
+
+#[cfg(test)]
+mod json_tests {
+    use serde_json::json;
+
+    use crate::core::diff_types::{
+        ArrayDiff, ArrayDiffDesc, Checker, Config, WorkingContext, WorkingFile,
+    };
+
+    use crate::json::diff_types::CheckingData;
+
+    const FILE_NAME_A: &str = "a.json";
+    const FILE_NAME_B: &str = "b.json";
+
+    #[test]
+    fn test_find_array_diffs() {
+        // arrange
+        let a = json!({
+            "no_diff_array": [
+                1, 2, 3, 4
+            ],
+            "diff_array": [
+                1, 2, 3, 4
+            ],
+            "nested": {
+                "no_diff_array": [
+                    1, 2, 3, 4
+                ],
+                "diff_array": [
+                    1, 2, 3, 4
+                ],
+            },
+        });
+
+        let b = json!({
+            "no_diff_array": [
+                1, 2, 3, 4
+            ],
+            "diff_array": [
+                1, 2, 8, 4
+            ],
+            "nested": {
+                "no_diff_array": [
+                    1, 2, 3, 4
+                ],
+                "diff_array": [
+                    1, 2, 8, 4
+                ],
+            },
+        });
+
+        let expected = vec![
+            ArrayDiff::new("diff_array".to_owned(), ArrayDiffDesc::AHas, "3".to_owned()),
+            ArrayDiff::new(
+                "diff_array".to_owned(),
+                ArrayDiffDesc::BMisses,
+                "3".to_owned(),
+            ),
+            ArrayDiff::new("diff_array".to_owned(), ArrayDiffDesc::BHas, "8".to_owned()),
+            ArrayDiff::new(
+                "diff_array".to_owned(),
+                ArrayDiffDesc::AMisses,
+                "8".to_owned(),
+            ),
+            ArrayDiff::new(
+                "nested.diff_array".to_owned(),
+                ArrayDiffDesc::AHas,
+                "3".to_owned(),
+            ),
+            ArrayDiff::new(
+                "nested.diff_array".to_owned(),
+                ArrayDiffDesc::BMisses,
+                "3".to_owned(),
+            ),
+            ArrayDiff::new(
+                "nested.diff_array".to_owned(),
+                ArrayDiffDesc::BHas,
+                "8".to_owned(),
+            ),
+            ArrayDiff::new(
+                "nested.diff_array".to_owned(),
+                ArrayDiffDesc::AMisses,
+                "8".to_owned(),
+            ),
+        ];
+
+        let working_context = create_test_working_context(false);
+        let mut array_checker = CheckingData::new(
+            "",
+            a.as_object().unwrap(),
+            b.as_object().unwrap(),
+            &working_context,
+        );
+
+        // act
+        array_checker.check();
+
+        // assert
+        assert_array(&expected, array_checker.diffs());
+    }
+
+    #[test]
+    fn test_find_array_diffs_multiple_entries_with_same_value() {
+        // arrange
+        let a = json!({
+            "no_diff_array": [
+                1, 2, 3, 4,
+            ],
+            "diff_array": [
+                1, 2, 3, 4
+            ],
+            "nested": {
+                "no_diff_array": [
+                    1, 2, 3, 4
+                ],
+                "diff_array": [
+                    1, 2, 3, 4
+                ],
+            },
+        });
+
+        let b = json!({
+            "no_diff_array": [
+                1, 2, 3, 4
+            ],
+            "diff_array": [
+                1, 1, 2, 3, 3, 3, 4,
+            ],
+            "nested": {
+                "no_diff_array": [
+                    1, 2, 3, 4
+                ],
+                "diff_array": [
+                    1, 1, 2, 3, 3, 3, 4,
+                ],
+            },
+        });
+
+        let expected = vec![
+            ArrayDiff::new("diff_array".to_owned(), ArrayDiffDesc::BHas, "1".to_owned()),
+            ArrayDiff::new("diff_array".to_owned(), ArrayDiffDesc::BHas, "3".to_owned()),
+            ArrayDiff::new("diff_array".to_owned(), ArrayDiffDesc::BHas, "3".to_owned()),
+            ArrayDiff::new(
+                "diff_array".to_owned(),
+                ArrayDiffDesc::AMisses,
+                "1".to_owned(),
+            ),
+            ArrayDiff::new(
+                "diff_array".to_owned(),
+                ArrayDiffDesc::AMisses,
+                "3".to_owned(),
+            ),
+            ArrayDiff::new(
+                "diff_array".to_owned(),
+                ArrayDiffDesc::AMisses,
+                "3".to_owned(),
+            ),
+            ArrayDiff::new(
+                "nested.diff_array".to_owned(),
+                ArrayDiffDesc::BHas,
+                "1".to_owned(),
+            ),
+            ArrayDiff::new(
+                "nested.diff_array".to_owned(),
+                ArrayDiffDesc::BHas,
+                "3".to_owned(),
+            ),
+            ArrayDiff::new(
+                "nested.diff_array".to_owned(),
+                ArrayDiffDesc::BHas,
+                "3".to_owned(),
+            ),
+            ArrayDiff::new(
+                "nested.diff_array".to_owned(),
+                ArrayDiffDesc::AMisses,
+                "1".to_owned(),
+            ),
+            ArrayDiff::new(
+                "nested.diff_array".to_owned(),
+                ArrayDiffDesc::AMisses,
+                "3".to_owned(),
+            ),
+            ArrayDiff::new(
+                "nested.diff_array".to_owned(),
+                ArrayDiffDesc::AMisses,
+                "3".to_owned(),
+            ),
+        ];
+
+        let working_context = create_test_working_context(false);
+        let mut array_checker = CheckingData::new(
+            "",
+            a.as_object().unwrap(),
+            b.as_object().unwrap(),
+            &working_context,
+        );
+
+        // act
+        array_checker.check();
+
+        // assert
+        assert_array(&expected, array_checker.diffs());
+    }
+
+    #[test]
+    fn test_find_array_diffs_strings_and_numbers_differ() {
+        // arrange
+        let a = json!({
+            "diff_array": ["a", "3", { "x": 1, "y": 2 }],
+        });
+
+        let b = json!({
+            "diff_array": ["a", 3, { "y": 2, "x": 1 }],
+        });
+
+        let expected = vec![
+            ArrayDiff::new("diff_array".to_owned(), ArrayDiffDesc::AHas, "3".to_owned()),
+            ArrayDiff::new(
+                "diff_array".to_owned(),
+                ArrayDiffDesc::BMisses,
+                "3".to_owned(),
+            ),
+            ArrayDiff::new("diff_array".to_owned(), ArrayDiffDesc::BHas, "3".to_owned()),
+            ArrayDiff::new(
+                "diff_array".to_owned(),
+                ArrayDiffDesc::AMisses,
+                "3".to_owned(),
+            ),
+        ];
+
+        let working_context = create_test_working_context(false);
+        let mut array_checker = CheckingData::new(
+            "",
+            a.as_object().unwrap(),
+            b.as_object().unwrap(),
+            &working_context,
+        );
+
+        // act
+        array_checker.check();
+
+        // assert
+        assert_array(&expected, array_checker.diffs());
+    }
+
+    // Test utils
+
+    fn create_test_working_context(array_same_order: bool) -> WorkingContext {
+        let config = Config::new(array_same_order);
+        let working_file_a = WorkingFile::new(FILE_NAME_A.to_owned());
+        let working_file_b = WorkingFile::new(FILE_NAME_B.to_owned());
+        WorkingContext::new(working_file_a, working_file_b, config)
+    }
+
+    fn assert_array<T: PartialEq>(expected: &[T], result: &[T]) {
+        assert_eq!(expected.len(), result.len());
+        assert!(expected.iter().all(|item| result.contains(item)));
+    }
+}
+
+#[cfg(test)]
+mod yaml_tests {
+    use serde_yaml::{from_str, Mapping};
+
+    use crate::core::diff_types::{
+        ArrayDiff, ArrayDiffDesc, Checker, Config, WorkingContext, WorkingFile,
+    };
+
+    use crate::yaml::diff_types::CheckingData;
+
+    const FILE_NAME_A: &str = "a.json";
+    const FILE_NAME_B: &str = "b.json";
+
+    #[test]
+    fn test_find_array_diffs() {
+        // arrange
+        let a = from_str(
+            r"
+            'no_diff_array':
+                - 1
+                - 2
+                - 3
+                - 4
+            'diff_array':
+                - 1
+                - 2
+                - 3
+                - 4
+            'nested':
+                'no_diff_array':
+                    - 1
+                    - 2
+                    - 3
+                    - 4
+                'diff_array':
+                    - 1
+                    - 2
+                    - 3
+                    - 4
+        ",
+        )
+        .unwrap();
+
+        let b = from_str(
+            r"
+            'no_diff_array':
+                - 1
+                - 2
+                - 3
+                - 4
+            'diff_array':
+                - 1
+                - 2
+                - 8
+                - 4
+            'nested':
+                'no_diff_array':
+                    - 1
+                    - 2
+                    - 3
+                    - 4
+                'diff_array':
+                    - 1
+                    - 2
+                    - 8
+                    - 4
+        ",
+        )
+        .unwrap();
+
+        let expected = vec![
+            ArrayDiff::new("diff_array".to_owned(), ArrayDiffDesc::AHas, "3".to_owned()),
+            ArrayDiff::new(
+                "diff_array".to_owned(),
+                ArrayDiffDesc::BMisses,
+                "3".to_owned(),
+            ),
+            ArrayDiff::new("diff_array".to_owned(), ArrayDiffDesc::BHas, "8".to_owned()),
+            ArrayDiff::new(
+                "diff_array".to_owned(),
+                ArrayDiffDesc::AMisses,
+                "8".to_owned(),
+            ),
+            ArrayDiff::new(
+                "nested.diff_array".to_owned(),
+                ArrayDiffDesc::AHas,
+                "3".to_owned(),
+            ),
+            ArrayDiff::new(
+                "nested.diff_array".to_owned(),
+                ArrayDiffDesc::BMisses,
+                "3".to_owned(),
+            ),
+            ArrayDiff::new(
+                "nested.diff_array".to_owned(),
+                ArrayDiffDesc::BHas,
+                "8".to_owned(),
+            ),
+            ArrayDiff::new(
+                "nested.diff_array".to_owned(),
+                ArrayDiffDesc::AMisses,
+                "8".to_owned(),
+            ),
+        ];
+
+        let working_context = create_test_working_context(false);
+        let mut array_checker = CheckingData::new("", &a, &b, &working_context);
+
+        // act
+        array_checker.check();
+
+        // assert
+        assert_array(&expected, array_checker.diffs());
+    }
+
+    #[test]
+    fn test_find_array_diffs_multiple_entries_with_same_value() {
+        // arrange
+        let a: Mapping = from_str(
+            r"
+            'no_diff_array':
+                - 1
+                - 2
+                - 3
+                - 4
+            'diff_array':
+                - 1
+                - 2
+                - 3
+                - 4
+            'nested':
+                'no_diff_array':
+                    - 1
+                    - 2
+                    - 3
+                    - 4
+                'diff_array':
+                    - 1
+                    - 2
+                    - 3
+                    - 4
+        ",
+        )
+        .unwrap();
+
+        let b = from_str(
+            "
+            'no_diff_array':
+                - 1
+                - 2
+                - 3
+                - 4
+            'diff_array':
+                - 1
+                - 1
+                - 2
+                - 3
+                - 3
+                - 3
+                - 4
+            'nested':
+                'no_diff_array':
+                    - 1
+                    - 2
+                    - 3
+                    - 4
+                'diff_array':
+                    - 1
+                    - 1
+                    - 2
+                    - 3
+                    - 3
+                    - 3
+                    - 4
+        ",
+        )
+        .unwrap();
+
+        let expected = vec![
+            ArrayDiff::new("diff_array".to_owned(), ArrayDiffDesc::BHas, "1".to_owned()),
+            ArrayDiff::new("diff_array".to_owned(), ArrayDiffDesc::BHas, "3".to_owned()),
+            ArrayDiff::new("diff_array".to_owned(), ArrayDiffDesc::BHas, "3".to_owned()),
+            ArrayDiff::new(
+                "diff_array".to_owned(),
+                ArrayDiffDesc::AMisses,
+                "1".to_owned(),
+            ),
+            ArrayDiff::new(
+                "diff_array".to_owned(),
+                ArrayDiffDesc::AMisses,
+                "3".to_owned(),
+            ),
+            ArrayDiff::new(
+                "diff_array".to_owned(),
+                ArrayDiffDesc::AMisses,
+                "3".to_owned(),
+            ),
+            ArrayDiff::new(
+                "nested.diff_array".to_owned(),
+                ArrayDiffDesc::BHas,
+                "1".to_owned(),
+            ),
+            ArrayDiff::new(
+                "nested.diff_array".to_owned(),
+                ArrayDiffDesc::BHas,
+                "3".to_owned(),
+            ),
+            ArrayDiff::new(
+                "nested.diff_array".to_owned(),
+                ArrayDiffDesc::BHas,
+                "3".to_owned(),
+            ),
+            ArrayDiff::new(
+                "nested.diff_array".to_owned(),
+                ArrayDiffDesc::AMisses,
+                "1".to_owned(),
+            ),
+            ArrayDiff::new(
+                "nested.diff_array".to_owned(),
+                ArrayDiffDesc::AMisses,
+                "3".to_owned(),
+            ),
+            ArrayDiff::new(
+                "nested.diff_array".to_owned(),
+                ArrayDiffDesc::AMisses,
+                "3".to_owned(),
+            ),
+        ];
+
+        let working_context = create_test_working_context(false);
+        let mut array_checker = CheckingData::new("", &a, &b, &working_context);
+
+        // act
+        array_checker.check();
+
+        // assert
+        assert_array(&expected, array_checker.diffs());
+    }
+
+    // Test utils
+
+    fn create_test_working_context(array_same_order: bool) -> WorkingContext {
+        let config = Config::new(array_same_order);
+        let working_file_a = WorkingFile::new(FILE_NAME_A.to_owned());
+        let working_file_b = WorkingFile::new(FILE_NAME_B.to_owned());
+        WorkingContext::new(working_file_a, working_file_b, config)
+    }
+
+    fn assert_array<T: PartialEq>(expected: &[T], result: &[T]) {
+        assert_eq!(expected.len(), result.len());
+        assert!(expected.iter().all(|item| result.contains(item)));
+    }
+}
@@ -0,0 +1,685 @@
+/// Collects all the selected kinds of differences between 2 data sets in a single traversal.
+/// The result is the same as running the `KeyDiff`, `TypeDiff`, `ValueDiff` and `ArrayDiff` checkers one after the other, but the data is only walked once.
+///
+/// 1. We iterate through object `a`
+///     1. We construct a new path the same way the checkers do. If the config skips the path, we move on to the next key.
+///     2. If the key is missing from `b`, we store a `KeyDiff`.
+///     3. If the key is present in `b`, we compare the 2 fields:
+///         * If their types don't match, we store a `TypeDiff`.
+///         * If both are objects, we recursively start the process over for the nested objects.
+///         * If both are arrays and the user has specified, that arrays should be in the same order, we compare the items by index. Otherwise we store the `ArrayDiff` values for the items one of the arrays misses.
+///         * If the values aren't equal, we store a `ValueDiff`.
+/// 2. We iterate through object `b` and store a `KeyDiff` for each key missing from `a`, unless the config skips its path.
+///
+/// With include patterns set, the paths on the way to the included subtrees are walked too, but differences are only stored inside the subtrees.
+/// The exceptions are a key missing on the way to a subtree, if its value holds some of the included fields, and the arrays compared as a whole, since their items are only compared by the included fields.
+use crate::core::{
+    array_checker::find_array_diffs_in_arrays,
+    diff_types::{
+        ArrayDiff, ComparisionResult, Config, DiffCollection, DiffKinds, KeyDiff, TypeDiff,
+        ValueDiff, WorkingContext,
+    },
+    node::{arrays_equal_at, nodes_equal, Node, NodeObject},
+    path::Path,
+    record, record_items,
+};
+
+/// Runs all the difference checks selected in `diff_kinds` on `a` against `b` in a single traversal
+pub fn compare<A: Node, B: Node>(
+    a: &A::Object,
+    b: &B::Object,
+    working_context: &WorkingContext,
+    diff_kinds: DiffKinds,
+) -> ComparisionResult {
+    let mut comparison = Comparison::new(working_context, diff_kinds);
+    if !working_context.config.skips(&Path::root()) {
+        comparison.compare_objects::<A, B>(&Path::root(), a, b);
+    }
+    comparison.into_result()
+}
+
+/// Runs all the difference checks selected in `diff_kinds` on 2 values of any type in a single traversal.
+/// Objects are compared as in `compare`. Arrays go through the same ordered or unordered array logic as nested arrays.
+/// The differences of the outermost values themselves have `ROOT_KEY` as their key.
+pub fn compare_values<A: Node, B: Node>(
+    a: &A,
+    b: &B,
+    working_context: &WorkingContext,
+    diff_kinds: DiffKinds,
+) -> ComparisionResult {
+    let mut comparison = Comparison::new(working_context, diff_kinds);
+    if !working_context.config.skips(&Path::root()) {
+        comparison.compare_values(&Path::root(), a, b);
+    }
+    comparison.into_result()
+}
+
+/// Holds the differences collected during a single traversal
+pub struct Comparison<'a> {
+    key_diffs: DiffCollection<KeyDiff>,
+    type_diffs: DiffCollection<TypeDiff>,
+    value_diffs: DiffCollection<ValueDiff>,
+    array_diffs: DiffCollection<ArrayDiff>,
+    /// The kinds of differences, that should be collected
+    diff_kinds: DiffKinds,
+    /// Holds relevant data for the current run, such as file names, and user configs
+    working_context: &'a WorkingContext,
+}
+
+impl<'a> Comparison<'a> {
+    pub fn new(working_context: &'a WorkingContext, diff_kinds: DiffKinds) -> Comparison<'a> {
+        Comparison {
+            key_diffs: DiffCollection::new(),
+            type_diffs: DiffCollection::new(),
+            value_diffs: DiffCollection::new(),
+            array_diffs: DiffCollection::new(),
+            diff_kinds,
+            working_context,
+        }
+    }
+
+    /// Collects the differences between 2 objects. `key_in` is the path of the objects - the root path if they are the outermost ones.
+    pub fn compare_objects<A: Node, B: Node>(
+        &mut self,
+        key_in: &Path,
+        a: &A::Object,
+        b: &B::Object,
+    ) {
+        for (a_key, a_value) in a.entries() {
+            let key = key_in.with_key(&a_key);
+            if self.config().skips(&key) {
+                continue;
+            }
+
+            if let Some(b_value) = b.get(&a_key) {
+                self.compare_values(&key, a_value, b_value);
+            } else if self.diff_kinds.keys && self.config().reports_missing(&key, a_value) {
+                self.key_diffs.push(
+                    KeyDiff::new(
+                        key,
+                        self.working_context.file_a.name.clone(),
+                        self.working_context.file_b.name.clone(),
+                    )
+                    .with_json(record(self.config(), a_value)),
+                );
+            }
+        }
+
+        if self.diff_kinds.keys {
+            for (b_key, b_value) in b.entries() {
+                if a.get(&b_key).is_some() {
+                    continue;
+                }
+
+                let key = key_in.with_key(&b_key);
+                if self.config().reports_missing(&key, b_value) {
+                    self.key_diffs.push(
+                        KeyDiff::new(
+                            key,
+                            self.working_context.file_b.name.clone(),
+                            self.working_context.file_a.name.clone(),
+                        )
+                        .with_json(record(self.config(), b_value)),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Returns the collected differences
+    pub fn into_result(self) -> ComparisionResult {
+        (
+            self.key_diffs.into_diffs(),
+            self.type_diffs.into_diffs(),
+            self.value_diffs.into_diffs(),
+            self.array_diffs.into_diffs(),
+        )
+    }
+
+    fn compare_values<A: Node, B: Node>(&mut self, key_in: &Path, a: &A, b: &B) {
+        if self.diff_kinds.types {
+            let a_type = a.value_type();
+            let b_type = b.value_type();
+
+            if a_type != b_type && self.config().reports(key_in) {
+                self.type_diffs.push(
+                    TypeDiff::new(key_in.clone(), a_type.to_string(), b_type.to_string())
+                        .with_json(record(self.config(), a), record(self.config(), b)),
+                );
+            }
+        }
+
+        match (a.as_object(), b.as_object(), a.as_array(), b.as_array()) {
+            (Some(a_object), Some(b_object), _, _) => {
+                self.compare_objects::<A, B>(key_in, a_object, b_object);
+            }
+            (_, _, Some(a_array), Some(b_array)) => self.compare_arrays(key_in, a_array, b_array),
+            (_, _, None, None)
+                if self.diff_kinds.values
+                    && !nodes_equal(a, b)
+                    && self.config().reports(key_in) =>
+            {
+                self.value_diffs.push(
+                    ValueDiff::new(key_in.clone(), a.to_display_string(), b.to_display_string())
+                        .with_json(record(self.config(), a), record(self.config(), b)),
+                );
+            }
+            _ => {}
+        }
+    }
+
+    fn config(&self) -> &Config {
+        &self.working_context.config
+    }
+
+    fn compare_arrays<A: Node, B: Node>(&mut self, key_in: &Path, a: &[A], b: &[B]) {
+        if self.working_context.config.array_same_order && a.len() == b.len() {
+            for (index, a_item) in a.iter().enumerate() {
+                let key = key_in.with_index(index);
+                if !self.config().skips(&key) {
+                    self.compare_values(&key, a_item, &b[index]);
+                }
+            }
+            return;
+        }
+
+        if self.diff_kinds.values && !arrays_equal_at(a, b, key_in, self.config()) {
+            self.value_diffs.push(
+                ValueDiff::new(
+                    key_in.clone(),
+                    "Array differences present".to_owned(),
+                    "Array differences present".to_owned(),
+                )
+                .with_json(
+                    record_items(self.config(), a),
+                    record_items(self.config(), b),
+                ),
+            );
+        }
+
+        if self.diff_kinds.arrays && !self.working_context.config.array_same_order {
+            self.array_diffs
+                .append(&mut find_array_diffs_in_arrays(key_in, a, b, self.config()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use crate::{
+        core::{
+            diff_types::{
+                ArrayDiff, ArrayDiffDesc, Checker, Config, DiffKinds, KeyDiff, TypeDiff, ValueDiff,
+                WorkingContext, WorkingFile, ROOT_KEY,
+            },
+            path::{Path, PathSegment},
+        },
+        json::diff_types::CheckingData,
+    };
+
+    use super::{compare, compare_values};
+
+    const FILE_NAME_A: &str = "a.json";
+    const FILE_NAME_B: &str = "b.json";
+
+    #[test]
+    fn test_compare_matches_checkers_no_array_same_order() {
+        // arrange
+        let (a, b) = create_test_data();
+        let working_context = create_test_working_context(false);
+
+        // act
+        let result = compare::<Value, Value>(
+            a.as_object().unwrap(),
+            b.as_object().unwrap(),
+            &working_context,
+            DiffKinds::all(),
+        );
+
+        // assert
+        assert_matches_checkers(&a, &b, &working_context, result);
+    }
+
+    #[test]
+    fn test_compare_matches_checkers_array_same_order() {
+        // arrange
+        let (a, b) = create_test_data();
+        let working_context = create_test_working_context(true);
+
+        // act
+        let result = compare::<Value, Value>(
+            a.as_object().unwrap(),
+            b.as_object().unwrap(),
+            &working_context,
+            DiffKinds::all(),
+        );
+
+        // assert
+        assert_matches_checkers(&a, &b, &working_context, result);
+    }
+
+    #[test]
+    fn test_compare_matches_checkers_record_values() {
+        // arrange
+        let (a, b) = create_test_data();
+        let mut working_context = create_test_working_context(false);
+        working_context.config.record_values = true;
+
+        // act
+        let result = compare::<Value, Value>(
+            a.as_object().unwrap(),
+            b.as_object().unwrap(),
+            &working_context,
+            DiffKinds::all(),
+        );
+
+        // assert
+        assert!(result.0.iter().all(|diff| diff.json.is_some()));
+        assert!(result.2.iter().all(|diff| diff.json1.is_some()));
+        assert!(result.3.iter().all(|diff| diff.json.is_some()));
+        assert_matches_checkers(&a, &b, &working_context, result);
+    }
+
+    #[test]
+    fn test_compare_only_selected_kinds() {
+        // arrange
+        let (a, b) = create_test_data();
+        let working_context = create_test_working_context(false);
+
+        // act
+        let (key_diffs, type_diffs, value_diffs, array_diffs) = compare::<Value, Value>(
+            a.as_object().unwrap(),
+            b.as_object().unwrap(),
+            &working_context,
+            DiffKinds::new(true, false, false, true),
+        );
+
+        // assert
+        assert!(!key_diffs.is_empty());
+        assert!(type_diffs.is_empty());
+        assert!(value_diffs.is_empty());
+        assert!(!array_diffs.is_empty());
+    }
+
+    #[test]
+    fn test_compare_values_array_roots() {
+        // arrange
+        let a = json!([{ "id": 1, "name": "a" }, 2, 3]);
+        let b = json!([{ "id": 1, "name": "b" }, 2, 4]);
+
+        // act
+        let (ordered_key_diffs, _, ordered_value_diffs, ordered_array_diffs) =
+            compare_values(&a, &b, &create_test_working_context(true), DiffKinds::all());
+        let (_, _, unordered_value_diffs, unordered_array_diffs) = compare_values(
+            &a,
+            &b,
+            &create_test_working_context(false),
+            DiffKinds::all(),
+        );
+
+        // assert
+        assert!(ordered_key_diffs.is_empty());
+        assert_eq!(
+            vec![
+                ValueDiff::new("[0].name".to_owned(), "a".to_owned(), "b".to_owned()),
+                ValueDiff::new("[2]".to_owned(), "3".to_owned(), "4".to_owned()),
+            ],
+            ordered_value_diffs
+        );
+        assert!(ordered_array_diffs.is_empty());
+
+        assert_eq!(
+            vec![ValueDiff::new(
+                ROOT_KEY.to_owned(),
+                "Array differences present".to_owned(),
+                "Array differences present".to_owned()
+            )],
+            unordered_value_diffs
+        );
+        assert_eq!(8, unordered_array_diffs.len());
+        assert!(unordered_array_diffs.contains(&ArrayDiff::new(
+            ROOT_KEY.to_owned(),
+            ArrayDiffDesc::AHas,
+            "3".to_owned()
+        )));
+    }
+
+    #[test]
+    fn test_compare_values_scalar_roots() {
+        // act
+        let (key_diffs, type_diffs, value_diffs, array_diffs) = compare_values(
+            &json!("a"),
+            &json!(1),
+            &create_test_working_context(false),
+            DiffKinds::all(),
+        );
+
+        // assert
+        assert!(key_diffs.is_empty());
+        assert_eq!(
+            vec![TypeDiff::new(
+                ROOT_KEY.to_owned(),
+                "string".to_owned(),
+                "number".to_owned()
+            )],
+            type_diffs
+        );
+        assert_eq!(
+            vec![ValueDiff::new(
+                ROOT_KEY.to_owned(),
+                "a".to_owned(),
+                "1".to_owned()
+            )],
+            value_diffs
+        );
+        assert!(array_diffs.is_empty());
+    }
+
+    #[test]
+    fn test_compare_keys_with_special_characters() {
+        // arrange
+        let a = json!({ "labels": { "app.kubernetes.io/name": "web", "a[0]": 1 } });
+        let b = json!({ "labels": { "app.kubernetes.io/name": "api" }, "labels.a[0]": 1 });
+
+        // act
+        let (key_diffs, _, value_diffs, _) = compare::<Value, Value>(
+            a.as_object().unwrap(),
+            b.as_object().unwrap(),
+            &create_test_working_context(false),
+            DiffKinds::all(),
+        );
+
+        // assert
+        assert_eq!(
+            value_diffs[0].key.segments(),
+            &[
+                PathSegment::Key("labels".to_owned()),
+                PathSegment::Key("app.kubernetes.io/name".to_owned()),
+            ]
+        );
+        assert_eq!(
+            value_diffs[0].key.to_string(),
+            "labels.app\\.kubernetes\\.io/name"
+        );
+        assert_eq!(
+            vec![
+                KeyDiff::new(
+                    Path::root().with_key("labels").with_key("a[0]"),
+                    FILE_NAME_A.to_owned(),
+                    FILE_NAME_B.to_owned()
+                ),
+                KeyDiff::new(
+                    Path::root().with_key("labels.a[0]"),
+                    FILE_NAME_B.to_owned(),
+                    FILE_NAME_A.to_owned()
+                ),
+            ],
+            key_diffs
+        );
+    }
+
+    #[test]
+    fn test_compare_ignored_paths() {
+        // arrange
+        let a = json!({
+            "metadata": { "name": "web", "creationTimestamp": "1", "generation": 1 },
+            "spec": { "generation": 4, "replicas": 1 },
+            "items": [{ "name": "a", "status": "Running" }, { "name": "b", "status": "Pending" }]
+        });
+        let b = json!({
+            "metadata": { "name": "web", "creationTimestamp": "2", "generation": 2, "updateTimestamp": "3" },
+            "spec": { "generation": "5", "replicas": 3 },
+            "items": [{ "name": "a", "status": "Failed" }, { "name": "b" }]
+        });
+
+        for array_same_order in [false, true] {
+            let mut working_context = create_test_working_context(array_same_order);
+            working_context.config.ignore = vec![
+                "metadata.*Timestamp".into(),
+                "items[*].status".into(),
+                "**.generation".into(),
+            ];
+
+            // act
+            let result = compare::<Value, Value>(
+                a.as_object().unwrap(),
+                b.as_object().unwrap(),
+                &working_context,
+                DiffKinds::all(),
+            );
+
+            // assert
+            assert!(
+                result.0.is_empty(),
+                "array_same_order: {}",
+                array_same_order
+            );
+            assert!(
+                result.1.is_empty(),
+                "array_same_order: {}",
+                array_same_order
+            );
+            assert_eq!(
+                vec![ValueDiff::new(
+                    "spec.replicas".to_owned(),
+                    "1".to_owned(),
+                    "3".to_owned()
+                )],
+                result.2
+            );
+            assert!(
+                result.3.is_empty(),
+                "array_same_order: {}",
+                array_same_order
+            );
+            assert_matches_checkers(&a, &b, &working_context, result);
+        }
+    }
+
+    #[test]
+    fn test_compare_included_paths() {
+        // arrange
+        let a = json!({
+            "name": "web",
+            "spec": { "replicas": 1, "image": "web:1", "generation": 1 },
+            "status": { "ready": true },
+            "items": [{ "name": "a", "status": "Running" }, { "name": "b" }]
+        });
+        let b = json!({
+            "name": "api",
+            "spec": { "replicas": 3, "image": 2, "port": 80, "generation": 2 },
+            "items": [{ "name": "a", "status": "Failed" }, { "name": "c" }]
+        });
+
+        for array_same_order in [false, true] {
+            let mut working_context = create_test_working_context(array_same_order);
+            working_context.config.include = vec!["spec".into(), "items[*].name".into()];
+            working_context.config.ignore = vec!["**.generation".into()];
+
+            // act
+            let result = compare::<Value, Value>(
+                a.as_object().unwrap(),
+                b.as_object().unwrap(),
+                &working_context,
+                DiffKinds::all(),
+            );
+
+            // assert
+            assert_eq!(
+                vec![KeyDiff::new(
+                    "spec.port".to_owned(),
+                    FILE_NAME_B.to_owned(),
+                    FILE_NAME_A.to_owned()
+                )],
+                result.0
+            );
+            assert_eq!(
+                vec![TypeDiff::new(
+                    "spec.image".to_owned(),
+                    "string".to_owned(),
+                    "number".to_owned()
+                )],
+                result.1
+            );
+            let value_keys: Vec<String> =
+                result.2.iter().map(|diff| diff.key.to_string()).collect();
+            let items_key = if array_same_order {
+                "items[1].name"
+            } else {
+                "items"
+            };
+            assert_eq!(vec![items_key, "spec.image", "spec.replicas"], value_keys);
+            if array_same_order {
+                assert!(result.3.is_empty());
+            } else {
+                let array_diffs: Vec<(ArrayDiffDesc, &str)> = result
+                    .3
+                    .iter()
+                    .map(|diff| (diff.descriptor, diff.value.as_str()))
+                    .collect();
+                assert_eq!(
+                    vec![
+                        (ArrayDiffDesc::AHas, r#"{"name":"b"}"#),
+                        (ArrayDiffDesc::AMisses, r#"{"name":"c"}"#),
+                        (ArrayDiffDesc::BHas, r#"{"name":"c"}"#),
+                        (ArrayDiffDesc::BMisses, r#"{"name":"b"}"#),
+                    ],
+                    array_diffs
+                );
+            }
+            assert_matches_checkers(&a, &b, &working_context, result);
+        }
+    }
+
+    #[test]
+    fn test_compare_included_paths_missing_ancestor() {
+        // arrange
+        let a = json!({ "spec": { "replicas": 1 }, "status": { "ready": true } });
+        let b = json!({ "name": "web" });
+        let mut working_context = create_test_working_context(false);
+        working_context.config.include = vec!["spec.replicas".into()];
+
+        // act
+        let result = compare_values(&a, &b, &working_context, DiffKinds::all());
+
+        // assert
+        assert_eq!(
+            vec![KeyDiff::new(
+                "spec".to_owned(),
+                FILE_NAME_A.to_owned(),
+                FILE_NAME_B.to_owned()
+            )],
+            result.0
+        );
+        assert!(result.1.is_empty());
+        assert!(result.2.is_empty());
+        assert!(result.3.is_empty());
+        assert_matches_checkers(&a, &b, &working_context, result);
+    }
+
+    #[test]
+    fn test_compare_included_paths_missing_without_included_fields() {
+        // arrange
+        let a =
+            json!({ "x": 1, "spec": { "image": "web:1" }, "deps": { "core": { "version": 1 } } });
+        let b = json!({ "status": [{ "ready": true }] });
+        let mut working_context = create_test_working_context(false);
+        working_context.config.include = vec!["spec.replicas".into(), "**.version".into()];
+
+        // act
+        let result = compare_values(&a, &b, &working_context, DiffKinds::all());
+
+        // assert
+        assert_eq!(
+            vec![KeyDiff::new(
+                "deps".to_owned(),
+                FILE_NAME_A.to_owned(),
+                FILE_NAME_B.to_owned()
+            )],
+            result.0
+        );
+        assert_matches_checkers(&a, &b, &working_context, result);
+    }
+
+    // Test utils
+
+    fn create_test_data() -> (Value, Value) {
+        let a = json!({
+            "a_has": "a_has",
+            "diff_string": "a",
+            "a_string_b_int": "1",
+            "a_object_b_string": { "key": "value" },
+            "diff_array": [1, 2, 3, 4],
+            "diff_length_array": [1, 2, 3],
+            "array_of_objects": [
+                { "a_has": 1, "diff_number": 1 },
+                { "both_have": [1, 2] }
+            ],
+            "nested": {
+                "a_has": "a_has",
+                "diff_boolean": true,
+                "diff_array": ["a", "b"]
+            }
+        });
+        let b = json!({
+            "b_has": "b_has",
+            "diff_string": "b",
+            "a_string_b_int": 1,
+            "a_object_b_string": "value",
+            "diff_array": [1, 2, 8, 4],
+            "diff_length_array": [1, 2],
+            "array_of_objects": [
+                { "b_has": 1, "diff_number": 2 },
+                { "both_have": [2, 1] }
+            ],
+            "nested": {
+                "b_has": "b_has",
+                "diff_boolean": false,
+                "diff_array": ["b", "c"]
+            }
+        });
+
+        (a, b)
+    }
+
+    fn assert_matches_checkers(
+        a: &Value,
+        b: &Value,
+        working_context: &WorkingContext,
+        (key_diffs, type_diffs, value_diffs, array_diffs): (
+            Vec<KeyDiff>,
+            Vec<TypeDiff>,
+            Vec<ValueDiff>,
+            Vec<ArrayDiff>,
+        ),
+    ) {
+        let a = a.as_object().unwrap();
+        let b = b.as_object().unwrap();
+
+        let mut key_checker: CheckingData<KeyDiff> = CheckingData::new("", a, b, working_context);
+        let mut type_checker: CheckingData<TypeDiff> = CheckingData::new("", a, b, working_context);
+        let mut value_checker: CheckingData<ValueDiff> =
+            CheckingData::new("", a, b, working_context);
+        let mut array_checker: CheckingData<ArrayDiff> =
+            CheckingData::new("", a, b, working_context);
+
+        assert_array(key_checker.check_and_get().diffs(), &key_diffs);
+        assert_array(type_checker.check_and_get().diffs(), &type_diffs);
+        assert_array(value_checker.check_and_get().diffs(), &value_diffs);
+        assert_array(array_checker.check_and_get().diffs(), &array_diffs);
+    }
+
+    fn create_test_working_context(array_same_order: bool) -> WorkingContext {
+        let config = Config::new(array_same_order);
+        let working_file_a = WorkingFile::new(FILE_NAME_A.to_owned());
+        let working_file_b = WorkingFile::new(FILE_NAME_B.to_owned());
+        WorkingContext::new(working_file_a, working_file_b, config)
+    }
+
+    fn assert_array<T: PartialEq>(expected: &[T], result: &[T]) {
+        assert_eq!(expected.len(), result.len());
+        assert!(expected.iter().all(|item| result.contains(item)));
+    }
+}
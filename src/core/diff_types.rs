@@ -1,6 +1,16 @@
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::fmt;
 
+use crate::core::{
+    node::{Node, NodeObject},
+    path::Path,
+    pattern::PathPattern,
+};
+
+/// The rendered key of the differences found in the outermost values themselves, e.g. when comparing a root array or scalar
+pub const ROOT_KEY: &str = "$";
+
 pub trait Stringable {
     fn to_string(&self) -> String;
 }
@@ -22,14 +32,17 @@ pub trait Diff {}
 
 /// Used for tracking the types of fields in the read-in data
 /// It has a Display implementation for ease-of-use in dependent applications
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum ValueType {
     Null,
     Boolean,
     Number,
     String,
+    DateTime,
     Array,
     Object,
+    /// A value with an explicit tag, like YAML's `!Ref Foo`. Holds the tag, e.g. `!Ref`.
+    Tagged(String),
 }
 
 impl fmt::Display for ValueType {
@@ -39,8 +52,10 @@ impl fmt::Display for ValueType {
             ValueType::Boolean => "bool",
             ValueType::Number => "number",
             ValueType::String => "string",
+            ValueType::DateTime => "datetime",
             ValueType::Array => "array",
             ValueType::Object => "object",
+            ValueType::Tagged(tag) => tag,
         };
         write!(f, "{}", value_type_str)
     }
@@ -66,6 +81,10 @@ impl<T: Diff> DiffCollection<T> {
         &self.diffs
     }
 
+    pub fn into_diffs(self) -> Vec<T> {
+        self.diffs
+    }
+
     pub fn concatenate(&mut self, diffs: &mut DiffCollection<T>) {
         self.diffs.append(&mut diffs.diffs);
     }
@@ -95,28 +114,171 @@ pub enum ArrayDiffDesc {
     BMisses,
 }
 
+/// Holds the data required to run a difference check.
+/// `a` and `b` may come from different formats. The format specific modules expose it through their own `CheckingData` aliases.
+pub struct CheckingData<'a, T: Diff, A: Node, B: Node = A> {
+    /// Holds the collected differences
+    pub diffs: DiffCollection<T>,
+    /// Holds the path of the field currently checked - the root path if it's the outermost object
+    pub key: Path,
+    /// One of the 2 objects that should be checked
+    pub a: &'a A::Object,
+    /// One of the 2 objects that should be checked
+    pub b: &'a B::Object,
+    /// Holds relevant data for the current run, such as file names, and user configs
+    pub working_context: &'a WorkingContext,
+}
+
+impl<'a, T: Diff, A: Node, B: Node> CheckingData<'a, T, A, B> {
+    pub fn new(
+        key: impl Into<Path>,
+        a: &'a A::Object,
+        b: &'a B::Object,
+        working_context: &'a WorkingContext,
+    ) -> CheckingData<'a, T, A, B> {
+        let diff_collection: DiffCollection<T> = DiffCollection::new();
+        CheckingData {
+            diffs: diff_collection,
+            key: key.into(),
+            a,
+            b,
+            working_context,
+        }
+    }
+}
+
 /// Contains configuration options
-#[derive(Serialize, Deserialize, Clone, Copy)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct Config {
     /// Used for switching between one-by-one value comparison for arrays or has/misses kind of comparison
     pub array_same_order: bool,
+    /// Used for recording the compared values as JSON in the differences, so the differences can be applied later on
+    #[serde(default)]
+    pub record_values: bool,
+    /// The paths, that every check skips together with everything under them, e.g. `metadata.*Timestamp` or `**.generation`
+    #[serde(default)]
+    pub ignore: Vec<PathPattern>,
+    /// If not empty, only the subtrees matching these path patterns are checked, e.g. `spec` or `dependencies.*`
+    #[serde(default)]
+    pub include: Vec<PathPattern>,
 }
 
 impl Config {
     pub fn new(array_same_order: bool) -> Config {
-        Config { array_same_order }
+        Config {
+            array_same_order,
+            record_values: false,
+            ignore: vec![],
+            include: vec![],
+        }
+    }
+
+    /// Tells if the checks leave the path and everything under it out.
+    /// That's the case if it's ignored, or it's neither inside an included subtree nor on the way to one.
+    pub fn skips(&self, path: &Path) -> bool {
+        self.ignore.iter().any(|pattern| pattern.matches(path))
+            || !(self.include.is_empty()
+                || self
+                    .include
+                    .iter()
+                    .any(|pattern| pattern.covers(path) || pattern.leads_through(path)))
+    }
+
+    /// Tells if the differences found at the path are reported.
+    /// The paths on the way to the included subtrees are walked, but only the differences inside the subtrees are reported.
+    pub fn reports(&self, path: &Path) -> bool {
+        self.include.is_empty() || self.include.iter().any(|pattern| pattern.covers(path))
+    }
+
+    /// Tells if a key missing from one of the data sets is reported at the path, `value` being the value only one of them has.
+    /// Besides the paths inside the included subtrees, that's the case for the paths on the way to them, if the value holds some of the included fields.
+    pub fn reports_missing<N: Node>(&self, path: &Path, value: &N) -> bool {
+        if let Some(object) = value.as_object() {
+            return self.reports_missing_object::<N>(path, object);
+        }
+
+        !self.skips(path)
+            && (self.reports(path)
+                || value.as_array().is_some_and(|items| {
+                    items
+                        .iter()
+                        .enumerate()
+                        .any(|(index, item)| self.reports_missing(&path.with_index(index), item))
+                }))
+    }
+
+    /// Tells if a key missing from one of the data sets is reported at the path, like `reports_missing`, when its value is an object
+    pub fn reports_missing_object<N: Node>(&self, path: &Path, object: &N::Object) -> bool {
+        !self.skips(path)
+            && (self.reports(path)
+                || object
+                    .entries()
+                    .any(|(key, value)| self.reports_missing(&path.with_key(&key), value)))
+    }
+
+    /// Tells if any of the paths can be skipped, so the paths have to be checked at all
+    pub(crate) fn filters_paths(&self) -> bool {
+        !self.ignore.is_empty() || !self.include.is_empty()
+    }
+}
+
+/// Selects the kinds of differences, that should be collected by a comparison
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub struct DiffKinds {
+    /// Collect `KeyDiff` values
+    pub keys: bool,
+    /// Collect `TypeDiff` values
+    pub types: bool,
+    /// Collect `ValueDiff` values
+    pub values: bool,
+    /// Collect `ArrayDiff` values
+    pub arrays: bool,
+}
+
+impl DiffKinds {
+    pub fn new(keys: bool, types: bool, values: bool, arrays: bool) -> DiffKinds {
+        DiffKinds {
+            keys,
+            types,
+            values,
+            arrays,
+        }
+    }
+
+    /// Selects every kind of difference
+    pub fn all() -> DiffKinds {
+        DiffKinds::new(true, true, true, true)
+    }
+}
+
+impl Default for DiffKinds {
+    fn default() -> Self {
+        DiffKinds::all()
     }
 }
 
 /// Contains data about the file we're currently working with
 #[derive(Serialize, Deserialize, Clone)]
 pub struct WorkingFile {
+    /// The label of the data in the differences. Doesn't have to be a file path, e.g. `stdin` or `embedded defaults`.
     pub name: String,
+    /// The path of the file, if the data was read from one
+    #[serde(default)]
+    pub path: Option<String>,
 }
 
 impl WorkingFile {
+    /// Creates a working file with a label, that isn't tied to a file
     pub fn new(name: String) -> WorkingFile {
-        WorkingFile { name }
+        WorkingFile { name, path: None }
+    }
+
+    /// Creates a working file for a file on disk, labelled by its path
+    pub fn from_path(path: String) -> WorkingFile {
+        WorkingFile {
+            name: path.clone(),
+            path: Some(path),
+        }
     }
 }
 
@@ -138,17 +300,42 @@ impl WorkingContext {
     }
 }
 
+/// Deserializes a recorded value, that is present. A `null` is a recorded `null` value, the missing field is the unrecorded one.
+fn deserialize_recorded<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Value>, D::Error> {
+    Value::deserialize(deserializer).map(Some)
+}
+
 /// Stores differences in keys. Either a data-structure has a key present in the other or not.
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
 pub struct KeyDiff {
-    pub key: String,
+    pub key: Path,
     pub has: String,
     pub misses: String,
+    /// The value of the key in the data set, that has it, as JSON, recorded only if `Config::record_values` is set
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_recorded"
+    )]
+    pub json: Option<Value>,
 }
 
 impl KeyDiff {
-    pub fn new(key: String, has: String, misses: String) -> KeyDiff {
-        KeyDiff { key, has, misses }
+    pub fn new(key: impl Into<Path>, has: String, misses: String) -> KeyDiff {
+        KeyDiff {
+            key: key.into(),
+            has,
+            misses,
+            json: None,
+        }
+    }
+
+    /// Sets the recorded value
+    pub fn with_json(mut self, json: Option<Value>) -> KeyDiff {
+        self.json = json;
+        self
     }
 }
 
@@ -157,14 +344,41 @@ impl Diff for KeyDiff {}
 /// Stores differences in types. Used when a field with the same key has different types in the compared data.
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
 pub struct TypeDiff {
-    pub key: String,
+    pub key: Path,
     pub type1: String,
     pub type2: String,
+    /// The first value as JSON, recorded only if `Config::record_values` is set
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_recorded"
+    )]
+    pub json1: Option<Value>,
+    /// The second value as JSON, recorded only if `Config::record_values` is set
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_recorded"
+    )]
+    pub json2: Option<Value>,
 }
 
 impl TypeDiff {
-    pub fn new(key: String, type1: String, type2: String) -> TypeDiff {
-        TypeDiff { key, type1, type2 }
+    pub fn new(key: impl Into<Path>, type1: String, type2: String) -> TypeDiff {
+        TypeDiff {
+            key: key.into(),
+            type1,
+            type2,
+            json1: None,
+            json2: None,
+        }
+    }
+
+    /// Sets the recorded values
+    pub fn with_json(mut self, json1: Option<Value>, json2: Option<Value>) -> TypeDiff {
+        self.json1 = json1;
+        self.json2 = json2;
+        self
     }
 }
 
@@ -173,19 +387,42 @@ impl Diff for TypeDiff {}
 /// Stores differences in values. Used when a field with the same key has different values in the compared data.
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
 pub struct ValueDiff {
-    pub key: String,
+    pub key: Path,
     pub value1: String,
     pub value2: String,
+    /// The first value as JSON. Unlike `value1`, it holds the whole array for array differences, recorded only if `Config::record_values` is set
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_recorded"
+    )]
+    pub json1: Option<Value>,
+    /// The second value as JSON. Unlike `value2`, it holds the whole array for array differences, recorded only if `Config::record_values` is set
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_recorded"
+    )]
+    pub json2: Option<Value>,
 }
 
 impl ValueDiff {
-    pub fn new(key: String, value1: String, value2: String) -> ValueDiff {
+    pub fn new(key: impl Into<Path>, value1: String, value2: String) -> ValueDiff {
         ValueDiff {
-            key,
+            key: key.into(),
             value1,
             value2,
+            json1: None,
+            json2: None,
         }
     }
+
+    /// Sets the recorded values
+    pub fn with_json(mut self, json1: Option<Value>, json2: Option<Value>) -> ValueDiff {
+        self.json1 = json1;
+        self.json2 = json2;
+        self
+    }
 }
 
 impl Diff for ValueDiff {}
@@ -194,21 +431,76 @@ impl Diff for ValueDiff {}
 /// Only used when the user hasn't specified in the configs that the arrays should be in the same order.
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
 pub struct ArrayDiff {
-    pub key: String,
+    pub key: Path,
     pub descriptor: ArrayDiffDesc,
     pub value: String,
+    /// The item as JSON, recorded only if `Config::record_values` is set
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_recorded"
+    )]
+    pub json: Option<Value>,
 }
 
 impl ArrayDiff {
-    pub fn new(key: String, descriptor: ArrayDiffDesc, value: String) -> ArrayDiff {
+    pub fn new(key: impl Into<Path>, descriptor: ArrayDiffDesc, value: String) -> ArrayDiff {
         ArrayDiff {
-            key,
+            key: key.into(),
             descriptor,
             value,
+            json: None,
         }
     }
+
+    /// Sets the recorded item
+    pub fn with_json(mut self, json: Option<Value>) -> ArrayDiff {
+        self.json = json;
+        self
+    }
 }
 
 impl Diff for ArrayDiff {}
 
+/// Stores conflicts of a three-way merge. Used when both sides changed the same field differently compared to the base.
+/// The values are `None` if the field is missing from that side.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct ConflictDiff {
+    pub key: Path,
+    pub base: Option<String>,
+    pub ours: Option<String>,
+    pub theirs: Option<String>,
+}
+
+impl ConflictDiff {
+    pub fn new(
+        key: impl Into<Path>,
+        base: Option<String>,
+        ours: Option<String>,
+        theirs: Option<String>,
+    ) -> ConflictDiff {
+        ConflictDiff {
+            key: key.into(),
+            base,
+            ours,
+            theirs,
+        }
+    }
+}
+
+impl Diff for ConflictDiff {}
+
+/// Holds the result of a three-way merge. The conflicting fields have the value of `ours` in `merged`.
+#[derive(PartialEq, Debug, Clone)]
+pub struct MergeResult<N> {
+    pub merged: N,
+    pub conflicts: Vec<ConflictDiff>,
+}
+
+impl<N> MergeResult<N> {
+    pub fn new(merged: N, conflicts: Vec<ConflictDiff>) -> MergeResult<N> {
+        MergeResult { merged, conflicts }
+    }
+}
+
 pub type ComparisionResult = (Vec<KeyDiff>, Vec<TypeDiff>, Vec<ValueDiff>, Vec<ArrayDiff>);
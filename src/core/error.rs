@@ -0,0 +1,171 @@
+use std::{fmt, io};
+
+use crate::core::{diff_types::ValueType, path::Path};
+
+/// The deepest nesting of values the readers accept, the same as serde_json's limit for JSON
+pub(crate) const DEPTH_LIMIT: usize = 128;
+
+/// Result type returned by the public entry points of the lib
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Describes what went wrong while reading in or preparing data for comparison
+#[derive(Debug)]
+pub enum Error {
+    /// The data could not be read. `name` is the path of the file or the name of the source, that failed.
+    Io { name: String, source: io::Error },
+    /// The content is not valid in the expected format. `line` and `column` are 1 based, when known.
+    Parse {
+        format: &'static str,
+        message: String,
+        line: Option<usize>,
+        column: Option<usize>,
+    },
+    /// The outermost value of the data set has a type, that can't be compared
+    UnsupportedRoot {
+        format: &'static str,
+        found: ValueType,
+    },
+    /// The data exceeds one of the limits, that guard against malicious or broken input
+    LimitExceeded { format: &'static str, limit: String },
+    /// A patch can't be created or applied, because the value at `path` is missing or has the wrong type
+    Patch { path: Path, message: String },
+}
+
+impl Error {
+    pub(crate) fn io(name: &str, source: io::Error) -> Error {
+        Error::Io {
+            name: name.to_owned(),
+            source,
+        }
+    }
+
+    /// Returns the error of data nested deeper than `DEPTH_LIMIT`
+    pub(crate) fn depth_limit(format: &'static str) -> Error {
+        Error::LimitExceeded {
+            format,
+            limit: format!("nesting deeper than {} levels", DEPTH_LIMIT),
+        }
+    }
+
+    pub(crate) fn from_json(error: serde_json::Error) -> Error {
+        let message = error.to_string();
+
+        if message.starts_with("recursion limit exceeded") {
+            return Error::LimitExceeded {
+                format: "JSON",
+                limit: message,
+            };
+        }
+
+        Error::Parse {
+            format: "JSON",
+            message,
+            line: (error.line() > 0).then(|| error.line()),
+            column: (error.column() > 0).then(|| error.column()),
+        }
+    }
+
+    pub(crate) fn from_yaml(error: serde_yaml::Error) -> Error {
+        let message = error.to_string();
+
+        if message.starts_with("recursion limit exceeded")
+            || message.starts_with("repetition limit exceeded")
+            || message.contains(crate::yaml::VALUE_LIMIT_EXCEEDED)
+        {
+            return Error::LimitExceeded {
+                format: "YAML",
+                limit: message,
+            };
+        }
+
+        let location = error.location();
+        Error::Parse {
+            format: "YAML",
+            message,
+            line: location.as_ref().map(|location| location.line()),
+            column: location.as_ref().map(|location| location.column()),
+        }
+    }
+
+    pub(crate) fn from_json5(error: json5::Error) -> Error {
+        let json5::Error::Message { msg, location } = error;
+
+        Error::Parse {
+            format: "JSON5",
+            message: msg,
+            line: location.as_ref().map(|location| location.line),
+            column: location.as_ref().map(|location| location.column),
+        }
+    }
+
+    pub(crate) fn from_toml(error: ::toml::de::Error, content: &str) -> Error {
+        if error.message().starts_with("recursion limit exceeded") {
+            return Error::LimitExceeded {
+                format: "TOML",
+                limit: error.message().to_owned(),
+            };
+        }
+
+        let (line, column) = error
+            .span()
+            .map(|span| {
+                let (line, column) = position(content, span.start);
+                (Some(line), Some(column))
+            })
+            .unwrap_or((None, None));
+
+        Error::Parse {
+            format: "TOML",
+            message: error.message().to_owned(),
+            line,
+            column,
+        }
+    }
+}
+
+/// Calculates the 1 based line and column of a byte offset in the content
+pub(crate) fn position(content: &str, offset: usize) -> (usize, usize) {
+    let mut offset = offset.min(content.len());
+    while !content.is_char_boundary(offset) {
+        offset -= 1;
+    }
+
+    let before = &content[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before.chars().rev().take_while(|c| *c != '\n').count() + 1;
+    (line, column)
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { name, source } => write!(f, "Could not read {}: {}", name, source),
+            Error::Parse {
+                format, message, ..
+            } => write!(f, "Could not parse {}: {}", format, message),
+            Error::UnsupportedRoot { format, found } => write!(
+                f,
+                "Unsupported {} root: expected an object, found {}",
+                format, found
+            ),
+            Error::LimitExceeded { format, limit } => {
+                write!(f, "Limit exceeded while reading {}: {}", format, limit)
+            }
+            Error::Patch { path, message } => write!(
+                f,
+                "Could not patch the value at `{}`: {}",
+                path.to_json_pointer(),
+                message
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,298 @@
+/// Collects differences between the keys of 2 data sets.
+/// Stores `KeyDiff` values
+///
+/// 1. First we store all the keys in the `b` object, that the config reports as missing, into a set called `b_keys`
+/// 2. Then we go through all the fields of object `a`
+///     1. We construct a new path by adding the currently checked fields key to the path in our checker object. That's how we handle the keys of nested objects. If the config skips the path, we move on to the next field.
+///     2. If the key is in `b_keys`, we remove it from there
+///         * If the field is an object, we recursively call the same comparison and go through the new object
+///         * If the field is an array and the user defined the option that arrays have to be in the same order we iterate through the array and recursively repeat the checking process for each item. If we can't assume, that the arrays are in the same order, than this check is pointless.
+///     3. If the key is not present in `b_keys`, we save it to the `diffs` vector
+/// 3. After checking `a` we add all the remaining keys in `b_keys` to the diff vector, if they weren't removed, they aren't in a.
+use std::collections::HashMap;
+
+use crate::core::{
+    diff_types::{Checker, CheckingData, DiffCollection, KeyDiff},
+    node::{Node, NodeObject},
+    path::Path,
+    record,
+};
+
+impl<'a, A: Node, B: Node> Checker<KeyDiff> for CheckingData<'a, KeyDiff, A, B> {
+    fn check(&mut self) {
+        let mut b_keys = self.get_b_keys();
+        self.check_a(&mut b_keys);
+        self.check_b(&b_keys);
+    }
+
+    fn check_and_get(&mut self) -> &DiffCollection<KeyDiff> {
+        self.check();
+        &self.diffs
+    }
+
+    fn diffs(&self) -> &Vec<KeyDiff> {
+        self.diffs.diffs()
+    }
+}
+
+impl<'a, A: Node, B: Node> CheckingData<'a, KeyDiff, A, B> {
+    fn find_key_diffs_in_values(&mut self, key_in: &Path, a: &A, b: &B) {
+        if let (Some(a_object), Some(b_object)) = (a.as_object(), b.as_object()) {
+            self.find_key_diffs_in_objects(key_in, a_object, b_object);
+        }
+
+        if let (true, Some(a_array), Some(b_array)) = (
+            self.working_context.config.array_same_order,
+            a.as_array(),
+            b.as_array(),
+        ) {
+            if a_array.len() == b_array.len() {
+                self.find_key_diffs_in_arrays(key_in, a_array, b_array);
+            }
+        }
+    }
+
+    fn find_key_diffs_in_objects(&mut self, key_in: &Path, a: &A::Object, b: &B::Object) {
+        let mut key_checker: CheckingData<KeyDiff, A, B> =
+            CheckingData::new(key_in.clone(), a, b, self.working_context);
+
+        key_checker.check();
+        self.diffs.concatenate(&mut key_checker.diffs);
+    }
+
+    fn find_key_diffs_in_arrays(&mut self, key_in: &Path, a: &[A], b: &[B]) {
+        for (i, a_item) in a.iter().enumerate() {
+            let key = key_in.with_index(i);
+            if !self.working_context.config.skips(&key) {
+                self.find_key_diffs_in_values(&key, a_item, &b[i]);
+            }
+        }
+    }
+
+    fn get_b_keys(&self) -> HashMap<Path, String> {
+        self.b
+            .entries()
+            .filter_map(|(key, value)| {
+                let path = self.key.with_key(&key);
+                self.working_context
+                    .config
+                    .reports_missing(&path, value)
+                    .then(|| (path, key.into_owned()))
+            })
+            .collect()
+    }
+
+    fn check_a(&mut self, b_keys: &mut HashMap<Path, String>) {
+        for (a_key, a_value) in self.a.entries() {
+            let key = self.key.with_key(&a_key);
+            if self.working_context.config.skips(&key) {
+                continue;
+            }
+
+            if let Some(b_value) = self.b.get(&a_key) {
+                b_keys.remove(&key);
+                self.find_key_diffs_in_values(&key, a_value, b_value);
+            } else if self.working_context.config.reports_missing(&key, a_value) {
+                self.diffs.push(
+                    KeyDiff::new(
+                        key,
+                        self.working_context.file_a.name.clone(),
+                        self.working_context.file_b.name.clone(),
+                    )
+                    .with_json(record(&self.working_context.config, a_value)),
+                );
+            }
+        }
+    }
+
+    fn check_b(&mut self, b_keys: &HashMap<Path, String>) {
+        let mut remainder = b_keys
+            .iter()
+            .map(|(key, b_key)| {
+                KeyDiff::new(
+                    key.clone(),
+                    self.working_context.file_b.name.to_owned(),
+                    self.working_context.file_a.name.to_owned(),
+                )
+                .with_json(
+                    self.b
+                        .get(b_key)
+                        .and_then(|b_value| record(&self.working_context.config, b_value)),
+                )
+            })
+            .collect();
+
+        self.diffs.append(&mut remainder);
+    }
+}
+
+#[cfg(test)]
+mod json_tests {
+    use serde_json::json;
+
+    use crate::{
+        core::diff_types::{Checker, Config, KeyDiff, WorkingContext, WorkingFile},
+        json::diff_types::CheckingData,
+    };
+
+    const FILE_NAME_A: &str = "a.json";
+    const FILE_NAME_B: &str = "b.json";
+
+    #[test]
+    fn test_key_checker() {
+        // arrange
+        let a = json!({
+            "a_has": "a_has",
+            "both_have": "both_have",
+            "nested": {
+                "a_has": "a_has",
+                "both_have": "both_have"
+            }
+        });
+        let b = json!({
+            "b_has": "b_has",
+            "both_have": "both_have",
+            "nested": {
+                "b_has": "b_has",
+                "both_have": "both_have"
+            }
+        });
+
+        let expected = vec![
+            KeyDiff::new(
+                "a_has".to_owned(),
+                FILE_NAME_A.to_owned(),
+                FILE_NAME_B.to_owned(),
+            ),
+            KeyDiff::new(
+                "nested.a_has".to_owned(),
+                FILE_NAME_A.to_owned(),
+                FILE_NAME_B.to_owned(),
+            ),
+            KeyDiff::new(
+                "b_has".to_owned(),
+                FILE_NAME_B.to_owned(),
+                FILE_NAME_A.to_owned(),
+            ),
+            KeyDiff::new(
+                "nested.b_has".to_owned(),
+                FILE_NAME_B.to_owned(),
+                FILE_NAME_A.to_owned(),
+            ),
+        ];
+
+        let working_context = create_test_working_context(false);
+
+        let mut key_checker = CheckingData::new(
+            "",
+            a.as_object().unwrap(),
+            b.as_object().unwrap(),
+            &working_context,
+        );
+
+        // act
+        key_checker.check();
+
+        // assert
+        assert_array(&expected, key_checker.diffs());
+    }
+
+    // Test utils
+
+    fn create_test_working_context(array_same_order: bool) -> WorkingContext {
+        let config = Config::new(array_same_order);
+        let working_file_a = WorkingFile::new(FILE_NAME_A.to_owned());
+        let working_file_b = WorkingFile::new(FILE_NAME_B.to_owned());
+        WorkingContext::new(working_file_a, working_file_b, config)
+    }
+
+    fn assert_array<T: PartialEq>(expected: &[T], result: &[T]) {
+        assert_eq!(expected.len(), result.len());
+        assert!(expected.iter().all(|item| result.contains(item)));
+    }
+}
+
+#[cfg(test)]
+mod yaml_tests {
+    use serde_yaml::{from_str, Mapping};
+
+    use crate::{
+        core::diff_types::{Checker, Config, KeyDiff, WorkingContext, WorkingFile},
+        yaml::diff_types::CheckingData,
+    };
+
+    const FILE_NAME_A: &str = "a.json";
+    const FILE_NAME_B: &str = "b.json";
+
+    #[test]
+    fn test_key_checker() {
+        // arrange
+        let a: Mapping = from_str(
+            r"
+            'a_has': 'a_has'
+            'both_have': 'both_have'
+            'nested':
+                'a_has': 'a_has'
+                'both_have': 'both_have'
+        ",
+        )
+        .unwrap();
+        let b = from_str(
+            r"
+            'b_has': 'b_has'
+            'both_have': 'both_have'
+            'nested':
+                'b_has': 'b_has'
+                'both_have': 'both_have'
+        ",
+        )
+        .unwrap();
+
+        let expected = vec![
+            KeyDiff::new(
+                "a_has".to_owned(),
+                FILE_NAME_A.to_owned(),
+                FILE_NAME_B.to_owned(),
+            ),
+            KeyDiff::new(
+                "nested.a_has".to_owned(),
+                FILE_NAME_A.to_owned(),
+                FILE_NAME_B.to_owned(),
+            ),
+            KeyDiff::new(
+                "b_has".to_owned(),
+                FILE_NAME_B.to_owned(),
+                FILE_NAME_A.to_owned(),
+            ),
+            KeyDiff::new(
+                "nested.b_has".to_owned(),
+                FILE_NAME_B.to_owned(),
+                FILE_NAME_A.to_owned(),
+            ),
+        ];
+
+        let working_context = create_test_working_context(false);
+
+        let mut key_checker = CheckingData::new("", &a, &b, &working_context);
+
+        // act
+        key_checker.check();
+
+        // assert
+        assert_array(&expected, key_checker.diffs());
+    }
+
+    // Test utils
+
+    fn create_test_working_context(array_same_order: bool) -> WorkingContext {
+        let config = Config::new(array_same_order);
+        let working_file_a = WorkingFile::new(FILE_NAME_A.to_owned());
+        let working_file_b = WorkingFile::new(FILE_NAME_B.to_owned());
+        WorkingContext::new(working_file_a, working_file_b, config)
+    }
+
+    fn assert_array<T: PartialEq>(expected: &[T], result: &[T]) {
+        assert_eq!(expected.len(), result.len());
+        assert!(expected.iter().all(|item| result.contains(item)));
+    }
+}
@@ -0,0 +1,252 @@
+/// Merges the changes of 2 data sets, that were both derived from the same base.
+///
+/// 1. We run the comparison on `base` against `ours` and on `base` against `theirs` and collect the paths of all the differences as the changes of each side.
+/// 2. We start from a copy of `base` and apply all the changes of `ours` by copying the changed values over from `ours`.
+/// 3. We go through the changes of `theirs`:
+///     * If no change of `ours` overlaps it, meaning neither path is inside the other, we apply it by copying the value over from `theirs`.
+///     * If a change of `ours` overlaps it, we compare the values of the 2 sides at the shorter path. If they differ, we store a `ConflictDiff`. The merged data keeps the value of `ours` either way.
+use std::collections::HashSet;
+
+use crate::core::{
+    comparison::compare_values,
+    diff_types::{ConflictDiff, DiffKinds, MergeResult, WorkingContext},
+    node::{node_at, nodes_equal_at, Node},
+    path::{Path, PathSegment},
+};
+
+/// Abstraction over changing a value of a format in place, used for building the merged data
+pub trait MergeNode: Node + Clone {
+    /// Returns the field or item the segment points to, for changing it
+    fn child_mut(&mut self, segment: &PathSegment) -> Option<&mut Self>;
+
+    /// Copies the field with the key from the `source` object into this object, or removes it from this object if `source` doesn't have it
+    fn copy_field(&mut self, key: &str, source: &Self);
+}
+
+/// Merges the changes `ours` and `theirs` made to `base`.
+/// The files of the working context are only used as the names of the compared data sets, the config is used for the comparisons.
+pub fn merge<N: MergeNode>(
+    base: &N,
+    ours: &N,
+    theirs: &N,
+    working_context: &WorkingContext,
+) -> MergeResult<N> {
+    let our_changes = changed_paths(base, ours, working_context);
+    let their_changes = changed_paths(base, theirs, working_context);
+
+    let mut merged = base.clone();
+    let mut conflicts: Vec<ConflictDiff> = vec![];
+
+    for path in &our_changes {
+        apply_change(&mut merged, path, ours);
+    }
+
+    for their_path in &their_changes {
+        let mut overlapping = our_changes
+            .iter()
+            .filter(|our_path| our_path.starts_with(their_path) || their_path.starts_with(our_path))
+            .peekable();
+
+        if overlapping.peek().is_none() {
+            apply_change(&mut merged, their_path, theirs);
+            continue;
+        }
+
+        for our_path in overlapping {
+            let path = if our_path.starts_with(their_path) {
+                their_path
+            } else {
+                our_path
+            };
+
+            let our_value = node_at(ours, path);
+            let their_value = node_at(theirs, path);
+            let same = match (our_value, their_value) {
+                (Some(our_value), Some(their_value)) => {
+                    nodes_equal_at(our_value, their_value, path, &working_context.config)
+                }
+                (None, None) => true,
+                _ => false,
+            };
+
+            if !same && conflicts.iter().all(|conflict| &conflict.key != path) {
+                conflicts.push(ConflictDiff::new(
+                    path.clone(),
+                    node_at(base, path).map(Node::to_display_string),
+                    our_value.map(Node::to_display_string),
+                    their_value.map(Node::to_display_string),
+                ));
+            }
+        }
+    }
+
+    MergeResult::new(merged, conflicts)
+}
+
+/// Returns the paths of all the differences between `base` and `side` in the order they were found
+fn changed_paths<N: Node>(base: &N, side: &N, working_context: &WorkingContext) -> Vec<Path> {
+    let (key_diffs, type_diffs, value_diffs, array_diffs) =
+        compare_values(base, side, working_context, DiffKinds::all());
+
+    let mut seen = HashSet::new();
+    key_diffs
+        .into_iter()
+        .map(|diff| diff.key)
+        .chain(type_diffs.into_iter().map(|diff| diff.key))
+        .chain(value_diffs.into_iter().map(|diff| diff.key))
+        .chain(array_diffs.into_iter().map(|diff| diff.key))
+        .filter(|path| seen.insert(path.clone()))
+        .collect()
+}
+
+/// Copies the value at the path over from `side`, or removes it if `side` doesn't have it
+fn apply_change<N: MergeNode>(merged: &mut N, path: &Path, side: &N) {
+    match (path.parent(), path.last()) {
+        (Some(parent), Some(PathSegment::Key(key))) => {
+            if let (Some(target), Some(source)) =
+                (node_mut(merged, &parent), node_at(side, &parent))
+            {
+                target.copy_field(key, source);
+            }
+        }
+        (Some(_), Some(_)) => {
+            if let (Some(target), Some(value)) = (node_mut(merged, path), node_at(side, path)) {
+                *target = value.clone();
+            }
+        }
+        _ => *merged = side.clone(),
+    }
+}
+
+fn node_mut<'a, N: MergeNode>(value: &'a mut N, path: &Path) -> Option<&'a mut N> {
+    path.segments()
+        .iter()
+        .try_fold(value, |value, segment| value.child_mut(segment))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::core::diff_types::{Config, ConflictDiff, WorkingContext, WorkingFile};
+
+    use super::merge;
+
+    #[test]
+    fn test_merge_non_overlapping_changes() {
+        // arrange
+        let base = json!({
+            "image": "web:1.0",
+            "replicas": 1,
+            "env": { "LOG": "info" },
+            "ports": [80]
+        });
+        let ours = json!({
+            "image": "web:1.0",
+            "replicas": 3,
+            "env": { "LOG": "info", "DEBUG": "1" },
+            "ports": [80]
+        });
+        let theirs = json!({
+            "image": "web:2.0",
+            "replicas": 1,
+            "env": {},
+            "ports": [80, 443]
+        });
+
+        // act
+        let result = merge(&base, &ours, &theirs, &create_test_working_context(false));
+
+        // assert
+        assert!(result.conflicts.is_empty());
+        assert_eq!(
+            json!({
+                "image": "web:2.0",
+                "replicas": 3,
+                "env": { "DEBUG": "1" },
+                "ports": [80, 443]
+            }),
+            result.merged
+        );
+    }
+
+    #[test]
+    fn test_merge_conflicts() {
+        // arrange
+        let base = json!({ "replicas": 1, "env": { "LOG": "info" }, "same": 1, "items": [1, 2] });
+        let ours = json!({ "replicas": 3, "env": { "LOG": "debug" }, "same": 2, "items": [1, 5] });
+        let theirs = json!({ "replicas": 2, "same": 2, "items": [1, 2, 3] });
+
+        // act
+        let result = merge(&base, &ours, &theirs, &create_test_working_context(true));
+
+        // assert
+        assert_eq!(
+            json!({ "replicas": 3, "env": { "LOG": "debug" }, "same": 2, "items": [1, 5] }),
+            result.merged
+        );
+        assert_eq!(3, result.conflicts.len());
+        assert!(result.conflicts.contains(&ConflictDiff::new(
+            "replicas".to_owned(),
+            Some("1".to_owned()),
+            Some("3".to_owned()),
+            Some("2".to_owned())
+        )));
+        assert!(result.conflicts.contains(&ConflictDiff::new(
+            "env".to_owned(),
+            Some("{\"LOG\":\"info\"}".to_owned()),
+            Some("{\"LOG\":\"debug\"}".to_owned()),
+            None
+        )));
+        assert!(result.conflicts.contains(&ConflictDiff::new(
+            "items".to_owned(),
+            Some("[1,2]".to_owned()),
+            Some("[1,5]".to_owned()),
+            Some("[1,2,3]".to_owned())
+        )));
+    }
+
+    #[test]
+    fn test_merge_changes_inside_arrays() {
+        // arrange
+        let base = json!({ "containers": [{ "name": "a", "cpu": 1 }, { "name": "b", "cpu": 1 }] });
+        let ours = json!({ "containers": [{ "name": "a", "cpu": 2 }, { "name": "b", "cpu": 1 }] });
+        let theirs =
+            json!({ "containers": [{ "name": "a", "cpu": 1 }, { "name": "b", "cpu": 4 }] });
+
+        // act
+        let result = merge(&base, &ours, &theirs, &create_test_working_context(true));
+
+        // assert
+        assert!(result.conflicts.is_empty());
+        assert_eq!(
+            json!({ "containers": [{ "name": "a", "cpu": 2 }, { "name": "b", "cpu": 4 }] }),
+            result.merged
+        );
+    }
+
+    #[test]
+    fn test_merge_root_change() {
+        // arrange
+        let base = json!([1]);
+        let ours = json!([1]);
+        let theirs = json!("replaced");
+
+        // act
+        let result = merge(&base, &ours, &theirs, &create_test_working_context(false));
+
+        // assert
+        assert!(result.conflicts.is_empty());
+        assert_eq!(json!("replaced"), result.merged);
+    }
+
+    // Test utils
+
+    fn create_test_working_context(array_same_order: bool) -> WorkingContext {
+        WorkingContext::new(
+            WorkingFile::new("ours.json".to_owned()),
+            WorkingFile::new("theirs.json".to_owned()),
+            Config::new(array_same_order),
+        )
+    }
+}
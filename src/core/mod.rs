@@ -1 +1,36 @@
-pub mod diff_types;
\ No newline at end of file
+mod array_checker;
+pub mod comparison;
+pub mod diff_types;
+pub mod error;
+mod key_checker;
+pub mod merge;
+pub mod node;
+pub mod path;
+pub mod pattern;
+mod type_checker;
+mod value_checker;
+
+/// Returns the value as JSON, if the config asks for recording the values in the differences
+fn record<N: node::Node>(config: &diff_types::Config, value: &N) -> Option<serde_json::Value> {
+    config.record_values.then(|| node::to_json(value))
+}
+
+/// Returns the object as JSON, if the config asks for recording the values in the differences
+pub(crate) fn record_object<N: node::Node>(
+    config: &diff_types::Config,
+    object: &N::Object,
+) -> Option<serde_json::Value> {
+    config
+        .record_values
+        .then(|| node::object_to_json::<N>(object))
+}
+
+/// Returns the items of an array as JSON, if the config asks for recording the values in the differences
+fn record_items<N: node::Node>(
+    config: &diff_types::Config,
+    items: &[N],
+) -> Option<serde_json::Value> {
+    config
+        .record_values
+        .then(|| serde_json::Value::Array(items.iter().map(node::to_json).collect()))
+}
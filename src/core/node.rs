@@ -0,0 +1,254 @@
+use std::{borrow::Cow, collections::BTreeMap};
+
+use serde_json::{Number, Value};
+
+use crate::core::{
+    diff_types::{Config, ValueType},
+    path::{Path, PathSegment},
+};
+
+/// Abstraction over a single value of a read-in dataset.
+/// Every supported format implements this for its own value type, so the checkers in the `core` module can work on any of them.
+pub trait Node: Sized {
+    /// The format specific object (map) type, that holds the fields of an object value
+    type Object: NodeObject<Self>;
+
+    /// Returns the `ValueType` of the value
+    fn value_type(&self) -> ValueType;
+
+    /// Returns the fields, if the value is an object
+    fn as_object(&self) -> Option<&Self::Object>;
+
+    /// Returns the items, if the value is an array
+    fn as_array(&self) -> Option<&[Self]>;
+
+    /// Returns the value as it should appear in the differences.
+    /// Strings are returned as they are, without quotes or escaping.
+    fn to_display_string(&self) -> String;
+}
+
+/// Abstraction over the object (map) type of a format
+pub trait NodeObject<N: Node> {
+    /// Iterates through the fields of the object with their keys in string form
+    fn entries(&self) -> Box<dyn Iterator<Item = (Cow<'_, str>, &N)> + '_>;
+
+    /// Returns the value belonging to the key in string form, if present
+    fn get(&self, key: &str) -> Option<&N>;
+}
+
+/// Checks whether 2 values are equal. The values may come from different formats.
+pub(crate) fn nodes_equal<A: Node, B: Node>(a: &A, b: &B) -> bool {
+    equal(a, b, None)
+}
+
+/// Checks whether 2 values at the path are equal, leaving out the fields and items the config skips
+pub(crate) fn nodes_equal_at<A: Node, B: Node>(a: &A, b: &B, path: &Path, config: &Config) -> bool {
+    equal(a, b, Scope::new(path, config).as_ref())
+}
+
+/// Checks whether 2 arrays at the path have equal items in the same order, leaving out the fields and items the config skips
+pub(crate) fn arrays_equal_at<A: Node, B: Node>(
+    a: &[A],
+    b: &[B],
+    path: &Path,
+    config: &Config,
+) -> bool {
+    items_equal(a, b, Scope::new(path, config).as_ref())
+}
+
+/// The path of the values currently compared or fingerprinted, used for leaving out what the config skips
+struct Scope<'a> {
+    path: Path,
+    config: &'a Config,
+}
+
+impl<'a> Scope<'a> {
+    /// Returns no scope if the config doesn't skip any paths, so the paths aren't built at all
+    fn new(path: &Path, config: &'a Config) -> Option<Scope<'a>> {
+        config.filters_paths().then(|| Scope {
+            path: path.clone(),
+            config,
+        })
+    }
+
+    fn with_key(&self, key: &str) -> Scope<'a> {
+        Scope {
+            path: self.path.with_key(key),
+            config: self.config,
+        }
+    }
+
+    fn with_index(&self, index: usize) -> Scope<'a> {
+        Scope {
+            path: self.path.with_index(index),
+            config: self.config,
+        }
+    }
+
+    fn keeps(&self) -> bool {
+        !self.config.skips(&self.path)
+    }
+}
+
+fn equal<A: Node, B: Node>(a: &A, b: &B, scope: Option<&Scope>) -> bool {
+    if a.value_type() != b.value_type() {
+        return false;
+    }
+
+    match (a.as_object(), b.as_object(), a.as_array(), b.as_array()) {
+        (Some(a_object), Some(b_object), _, _) => {
+            let a_fields = kept_fields::<A>(a_object, scope);
+            let b_fields = kept_fields::<B>(b_object, scope);
+
+            a_fields.len() == b_fields.len()
+                && a_fields.into_iter().all(|(key, a_value, scope)| {
+                    b_object
+                        .get(&key)
+                        .is_some_and(|b_value| equal(a_value, b_value, scope.as_ref()))
+                })
+        }
+        (_, _, Some(a_array), Some(b_array)) => items_equal(a_array, b_array, scope),
+        (None, None, None, None) => a.to_display_string() == b.to_display_string(),
+        _ => false,
+    }
+}
+
+fn items_equal<A: Node, B: Node>(a: &[A], b: &[B], scope: Option<&Scope>) -> bool {
+    let a_items = kept_items(a, scope);
+    let b_items = kept_items(b, scope);
+
+    a_items.len() == b_items.len()
+        && a_items
+            .into_iter()
+            .zip(b_items)
+            .all(|((a_item, scope), (b_item, _))| equal(a_item, b_item, scope.as_ref()))
+}
+
+/// Returns the fields of the object, that the scope keeps, with their own scopes
+fn kept_fields<'n, 'a, N: Node>(
+    object: &'n N::Object,
+    scope: Option<&Scope<'a>>,
+) -> Vec<(Cow<'n, str>, &'n N, Option<Scope<'a>>)> {
+    object
+        .entries()
+        .map(|(key, value)| {
+            let scope = scope.map(|scope| scope.with_key(&key));
+            (key, value, scope)
+        })
+        .filter(|(_, _, scope)| scope.as_ref().is_none_or(Scope::keeps))
+        .collect()
+}
+
+/// Returns the items of the array, that the scope keeps, with their own scopes
+fn kept_items<'n, 'a, N: Node>(
+    items: &'n [N],
+    scope: Option<&Scope<'a>>,
+) -> Vec<(&'n N, Option<Scope<'a>>)> {
+    items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| (item, scope.map(|scope| scope.with_index(index))))
+        .filter(|(_, scope)| scope.as_ref().is_none_or(Scope::keeps))
+        .collect()
+}
+
+/// Returns the value at the path inside the value, if present. Selectors don't point to a single value, so paths with them are never found.
+pub(crate) fn node_at<'a, N: Node>(value: &'a N, path: &Path) -> Option<&'a N> {
+    path.segments().iter().try_fold(value, child)
+}
+
+/// Returns the identity of a record or document from the value at the path inside the object, if present.
+/// The value is rendered as JSON, so values of different types, like `1` and `"1"`, have different identities.
+pub(crate) fn identity_at<N: Node>(object: &N::Object, path: &Path) -> Option<String> {
+    let (PathSegment::Key(key), rest) = path.segments().split_first()? else {
+        return None;
+    };
+    let value = rest.iter().try_fold(object.get(key)?, child)?;
+    Some(to_json(value).to_string())
+}
+
+fn child<'a, N: Node>(value: &'a N, segment: &PathSegment) -> Option<&'a N> {
+    match segment {
+        PathSegment::Key(key) => value.as_object()?.get(key),
+        PathSegment::Index(index) => value.as_array()?.get(*index),
+        PathSegment::Selector(_) => None,
+    }
+}
+
+/// Converts the value into JSON. Numbers, that JSON can't hold, and scalars with tags are kept as their display strings.
+pub(crate) fn to_json<N: Node>(value: &N) -> Value {
+    if let Some(items) = value.as_array() {
+        return Value::Array(items.iter().map(to_json).collect());
+    }
+
+    if let Some(object) = value.as_object() {
+        return object_to_json::<N>(object);
+    }
+
+    let display = value.to_display_string();
+    match value.value_type() {
+        ValueType::Null => Value::Null,
+        ValueType::Boolean => Value::Bool(display == "true"),
+        ValueType::Number => display
+            .parse::<Number>()
+            .map_or(Value::String(display), Value::Number),
+        _ => Value::String(display),
+    }
+}
+
+/// Converts the object into a JSON object
+pub(crate) fn object_to_json<N: Node>(object: &N::Object) -> Value {
+    Value::Object(
+        object
+            .entries()
+            .map(|(key, value)| (key.into_owned(), to_json(value)))
+            .collect(),
+    )
+}
+
+/// Creates a string for each item of the array at the path, that identifies the item together with its type.
+/// Two items have the same fingerprint if they are equal, regardless of the order of their object fields.
+/// The items and fields the config skips are left out. Used for counting array items.
+pub(crate) fn fingerprint_items<'n, N: Node>(
+    items: &'n [N],
+    path: &Path,
+    config: &Config,
+) -> Vec<(&'n N, String)> {
+    kept_items(items, Scope::new(path, config).as_ref())
+        .into_iter()
+        .map(|(item, scope)| (item, fingerprint_in(item, scope.as_ref())))
+        .collect()
+}
+
+fn fingerprint_in<N: Node>(value: &N, scope: Option<&Scope>) -> String {
+    match value.value_type() {
+        ValueType::Tagged(tag) => format!("{} {}", tag, fingerprint_untagged(value, scope)),
+        _ => fingerprint_untagged(value, scope),
+    }
+}
+
+fn fingerprint_untagged<N: Node>(value: &N, scope: Option<&Scope>) -> String {
+    if let Some(array) = value.as_array() {
+        let items: Vec<String> = kept_items(array, scope)
+            .into_iter()
+            .map(|(item, scope)| fingerprint_in(item, scope.as_ref()))
+            .collect();
+        format!("[{}]", items.join(","))
+    } else if let Some(object) = value.as_object() {
+        let fields: BTreeMap<String, String> = kept_fields::<N>(object, scope)
+            .into_iter()
+            .map(|(key, value, scope)| {
+                (format!("{:?}", key), fingerprint_in(value, scope.as_ref()))
+            })
+            .collect();
+        let fields: Vec<String> = fields
+            .into_iter()
+            .map(|(key, value)| format!("{}:{}", key, value))
+            .collect();
+        format!("{{{}}}", fields.join(","))
+    } else if value.value_type() == ValueType::String {
+        format!("{:?}", value.to_display_string())
+    } else {
+        value.to_display_string()
+    }
+}
@@ -0,0 +1,588 @@
+use std::{convert::Infallible, fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::{
+    diff_types::ROOT_KEY,
+    error::{Error, Result},
+};
+
+/// A single step from a value to one of its children
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone)]
+pub enum PathSegment {
+    /// The field of an object with the given key
+    Key(String),
+    /// The item of an array at the given position
+    Index(usize),
+    /// The item of a collection picked by its identity instead of its position, e.g. `id=42` of a table row
+    Selector(String),
+}
+
+/// The location of a value inside a data set as a sequence of segments. The empty path points to the outermost value.
+///
+/// The string form joins keys with `.`, wraps indexes and selectors in `[]` and renders the empty path as `ROOT_KEY`. An empty first key is marked with a leading `.`, e.g. `.` or `..name`.
+/// The characters `\`, `.`, `[` and `]` are escaped with a `\` inside keys, so `Path::parse` gets back the exact segments.
+/// The path can be rendered as a JSON Pointer or a JSONPath as well. Selectors have no equivalent in those notations, so they are rendered as keys, except for the `*` wildcard in JSONPath.
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Default)]
+#[serde(into = "String", from = "String")]
+pub struct Path {
+    segments: Vec<PathSegment>,
+}
+
+impl Path {
+    pub fn new(segments: Vec<PathSegment>) -> Path {
+        Path { segments }
+    }
+
+    /// Returns the path of the outermost value
+    pub fn root() -> Path {
+        Path::default()
+    }
+
+    /// Parses the string form of a path. Malformed escapes and brackets are kept as literal text.
+    pub fn parse(text: &str) -> Path {
+        if text.is_empty() || text == ROOT_KEY {
+            return Path::root();
+        }
+
+        let mut segments = vec![];
+        let mut key = String::new();
+        let mut key_open = false;
+        let mut chars = text.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => {
+                    key.push(chars.next().unwrap_or('\\'));
+                    key_open = true;
+                }
+                // A leading `.` marks an empty first key, so the lone empty key isn't the root path
+                '.' if !key_open && segments.is_empty() => {
+                    segments.push(PathSegment::Key(String::new()));
+                }
+                '.' => {
+                    if key_open {
+                        segments.push(PathSegment::Key(std::mem::take(&mut key)));
+                    }
+                    key_open = true;
+                }
+                '[' => {
+                    if key_open {
+                        segments.push(PathSegment::Key(std::mem::take(&mut key)));
+                        key_open = false;
+                    }
+                    segments.push(parse_bracket(&mut chars));
+                }
+                _ => {
+                    key.push(c);
+                    key_open = true;
+                }
+            }
+        }
+
+        if key_open {
+            segments.push(PathSegment::Key(key));
+        }
+
+        Path { segments }
+    }
+
+    /// Parses an RFC 6901 JSON Pointer, like `/spec/containers/0/image`.
+    /// Tokens, that are valid array indexes become `PathSegment::Index` values, every other token is a key.
+    ///
+    /// # Errors
+    /// Returns an error if the pointer is neither empty nor starts with a `/`, or it has a `~` not followed by `0` or `1`.
+    pub fn from_json_pointer(pointer: &str) -> Result<Path> {
+        if pointer.is_empty() {
+            return Ok(Path::root());
+        }
+
+        let Some(tokens) = pointer.strip_prefix('/') else {
+            return Err(pointer_error("a JSON Pointer must start with `/`", 1));
+        };
+
+        let mut segments = vec![];
+        let mut column = 2;
+
+        for token in tokens.split('/') {
+            segments.push(pointer_segment(token, column)?);
+            column += token.chars().count() + 1;
+        }
+
+        Ok(Path { segments })
+    }
+
+    /// Renders the path as an RFC 6901 JSON Pointer, like `/spec/containers/0/image`. The root path is the empty pointer.
+    pub fn to_json_pointer(&self) -> String {
+        self.segments
+            .iter()
+            .map(|segment| {
+                let token = match segment {
+                    PathSegment::Key(key) | PathSegment::Selector(key) => {
+                        key.replace('~', "~0").replace('/', "~1")
+                    }
+                    PathSegment::Index(index) => index.to_string(),
+                };
+                format!("/{}", token)
+            })
+            .collect()
+    }
+
+    /// Renders the path as a normalized JSONPath, like `$.spec.containers[0].image`. Keys, that aren't plain identifiers are written as `['key']`.
+    pub fn to_json_path(&self) -> String {
+        let mut json_path = String::from("$");
+
+        for segment in &self.segments {
+            match segment {
+                PathSegment::Index(index) => json_path.push_str(&format!("[{}]", index)),
+                PathSegment::Selector(selector) if selector == "*" => json_path.push_str("[*]"),
+                PathSegment::Key(key) | PathSegment::Selector(key) if is_identifier(key) => {
+                    json_path.push('.');
+                    json_path.push_str(key);
+                }
+                PathSegment::Key(key) | PathSegment::Selector(key) => {
+                    json_path.push_str("['");
+                    for c in key.chars() {
+                        match c {
+                            '\\' | '\'' => {
+                                json_path.push('\\');
+                                json_path.push(c);
+                            }
+                            '\n' => json_path.push_str("\\n"),
+                            '\r' => json_path.push_str("\\r"),
+                            '\t' => json_path.push_str("\\t"),
+                            c if c.is_control() => {
+                                json_path.push_str(&format!("\\u{:04x}", c as u32))
+                            }
+                            c => json_path.push(c),
+                        }
+                    }
+                    json_path.push_str("']");
+                }
+            }
+        }
+
+        json_path
+    }
+
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.segments
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Returns the path of the value, that holds the value at this path, or `None` for the root path
+    pub fn parent(&self) -> Option<Path> {
+        let (_, segments) = self.segments.split_last()?;
+        Some(Path::new(segments.to_vec()))
+    }
+
+    /// Returns the last segment of the path, or `None` for the root path
+    pub fn last(&self) -> Option<&PathSegment> {
+        self.segments.last()
+    }
+
+    /// Tells if the path is this path or points inside the value at this path
+    pub fn starts_with(&self, prefix: &Path) -> bool {
+        self.segments.starts_with(&prefix.segments)
+    }
+
+    /// Returns the path of the field `key` of the object at this path
+    pub fn with_key(&self, key: &str) -> Path {
+        self.with_segment(PathSegment::Key(key.to_owned()))
+    }
+
+    /// Returns the path of the item at `index` of the array at this path
+    pub fn with_index(&self, index: usize) -> Path {
+        self.with_segment(PathSegment::Index(index))
+    }
+
+    /// Returns the path of the item picked by `selector` from the collection at this path
+    pub fn with_selector(&self, selector: &str) -> Path {
+        self.with_segment(PathSegment::Selector(selector.to_owned()))
+    }
+
+    fn with_segment(&self, segment: PathSegment) -> Path {
+        let mut segments = Vec::with_capacity(self.segments.len() + 1);
+        segments.extend_from_slice(&self.segments);
+        segments.push(segment);
+        Path { segments }
+    }
+}
+
+/// Reads the content of a `[]` segment up to the closing bracket. Unescaped digits are an index, anything else is a selector.
+fn parse_bracket(chars: &mut std::str::Chars) -> PathSegment {
+    let mut content = String::new();
+    let mut escaped = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                content.push(chars.next().unwrap_or('\\'));
+                escaped = true;
+            }
+            ']' => break,
+            _ => content.push(c),
+        }
+    }
+
+    if !escaped && !content.is_empty() && content.bytes().all(|b| b.is_ascii_digit()) {
+        if let Ok(index) = content.parse() {
+            return PathSegment::Index(index);
+        }
+    }
+
+    PathSegment::Selector(content)
+}
+
+/// Turns a single reference token of a JSON Pointer into a segment. `column` is the 1 based position of the token in the pointer.
+fn pointer_segment(token: &str, column: usize) -> Result<PathSegment> {
+    let is_index = token == "0"
+        || (!token.is_empty()
+            && !token.starts_with('0')
+            && token.bytes().all(|b| b.is_ascii_digit()));
+    if is_index {
+        if let Ok(index) = token.parse() {
+            return Ok(PathSegment::Index(index));
+        }
+    }
+
+    let mut key = String::with_capacity(token.len());
+    let mut chars = token.chars().enumerate();
+
+    while let Some((position, c)) = chars.next() {
+        if c != '~' {
+            key.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some((_, '0')) => key.push('~'),
+            Some((_, '1')) => key.push('/'),
+            _ => {
+                return Err(pointer_error(
+                    "`~` must be followed by `0` or `1`",
+                    column + position,
+                ))
+            }
+        }
+    }
+
+    Ok(PathSegment::Key(key))
+}
+
+fn pointer_error(message: &str, column: usize) -> Error {
+    Error::Parse {
+        format: "JSON Pointer",
+        message: message.to_owned(),
+        line: Some(1),
+        column: Some(column),
+    }
+}
+
+/// Tells if the key can be written in the dot notation of JSONPath
+fn is_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn write_escaped(f: &mut fmt::Formatter<'_>, text: &str, special: &[char]) -> fmt::Result {
+    for c in text.chars() {
+        if c == '\\' || special.contains(&c) {
+            write!(f, "\\")?;
+        }
+        write!(f, "{}", c)?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.segments.as_slice() {
+            [] => return write!(f, "{}", ROOT_KEY),
+            [PathSegment::Key(key)] if key == ROOT_KEY => return write!(f, "\\{}", key),
+            _ => {}
+        }
+
+        for (position, segment) in self.segments.iter().enumerate() {
+            match segment {
+                PathSegment::Key(key) => {
+                    if position > 0 || key.is_empty() {
+                        write!(f, ".")?;
+                    }
+                    write_escaped(f, key, &['.', '[', ']'])?;
+                }
+                PathSegment::Index(index) => write!(f, "[{}]", index)?,
+                PathSegment::Selector(selector) => {
+                    write!(f, "[")?;
+                    if !selector.is_empty() && selector.bytes().all(|b| b.is_ascii_digit()) {
+                        write!(f, "\\")?;
+                    }
+                    write_escaped(f, selector, &[']'])?;
+                    write!(f, "]")?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Path {
+    type Err = Infallible;
+
+    fn from_str(text: &str) -> std::result::Result<Path, Infallible> {
+        Ok(Path::parse(text))
+    }
+}
+
+impl From<&str> for Path {
+    fn from(text: &str) -> Path {
+        Path::parse(text)
+    }
+}
+
+impl From<String> for Path {
+    fn from(text: String) -> Path {
+        Path::parse(&text)
+    }
+}
+
+impl From<Path> for String {
+    fn from(path: Path) -> String {
+        path.to_string()
+    }
+}
+
+/// Serializes a `Path` as an RFC 6901 JSON Pointer. Use it with `#[serde(with = "libdtf::core::path::json_pointer")]`.
+pub mod json_pointer {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    use super::Path;
+
+    pub fn serialize<S: Serializer>(path: &Path, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&path.to_json_pointer())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Path, D::Error> {
+        let pointer = String::deserialize(deserializer)?;
+        Path::from_json_pointer(&pointer).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::error::Error;
+
+    use super::{Path, PathSegment};
+
+    #[test]
+    fn test_render_nested_path() {
+        // arrange
+        let path = Path::root().with_key("a").with_index(0).with_key("b");
+
+        // act
+        let result = path.to_string();
+
+        // assert
+        assert_eq!(result, "a[0].b");
+    }
+
+    #[test]
+    fn test_render_root() {
+        // arrange
+        let path = Path::root();
+
+        // act
+        let result = path.to_string();
+
+        // assert
+        assert_eq!(result, "$");
+        assert_eq!(Path::parse("$"), Path::root());
+        assert_eq!(Path::parse(""), Path::root());
+    }
+
+    #[test]
+    fn test_render_escapes_special_characters() {
+        // arrange
+        let path = Path::root()
+            .with_key("metadata")
+            .with_key("labels")
+            .with_key("app.kubernetes.io/name");
+
+        // act
+        let result = path.to_string();
+
+        // assert
+        assert_eq!(result, "metadata.labels.app\\.kubernetes\\.io/name");
+    }
+
+    #[test]
+    fn test_parse_escaped_path() {
+        // arrange
+        let text = "metadata.labels.app\\.kubernetes\\.io/name";
+
+        // act
+        let result = Path::parse(text);
+
+        // assert
+        assert_eq!(
+            result.segments(),
+            &[
+                PathSegment::Key("metadata".to_owned()),
+                PathSegment::Key("labels".to_owned()),
+                PathSegment::Key("app.kubernetes.io/name".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_indexes_and_selectors() {
+        // arrange
+        let text = "rows[id=42].items[3]";
+
+        // act
+        let result = Path::parse(text);
+
+        // assert
+        assert_eq!(
+            result,
+            Path::root()
+                .with_key("rows")
+                .with_selector("id=42")
+                .with_key("items")
+                .with_index(3)
+        );
+    }
+
+    #[test]
+    fn test_round_trip_unusual_segments() {
+        // arrange
+        let paths = vec![
+            Path::root().with_key("a[0]").with_key("b\\c"),
+            Path::root().with_key("").with_key("x"),
+            Path::root().with_key(""),
+            Path::root().with_key("").with_key(""),
+            Path::root().with_key("").with_index(0),
+            Path::root().with_key("a").with_key(""),
+            Path::root().with_key("$"),
+            Path::root().with_key("$").with_key("b"),
+            Path::root().with_index(2).with_key("a.b"),
+            Path::root().with_selector("7").with_selector("x]y"),
+            Path::root().with_key("a").with_index(0).with_index(1),
+        ];
+
+        for path in paths {
+            // act
+            let result = Path::parse(&path.to_string());
+
+            // assert
+            assert_eq!(result, path, "{}", path);
+        }
+    }
+
+    #[test]
+    fn test_render_json_pointer() {
+        // arrange
+        let path = Path::root()
+            .with_key("spec")
+            .with_key("containers")
+            .with_index(0)
+            .with_key("a/b~c");
+
+        // act
+        let result = path.to_json_pointer();
+
+        // assert
+        assert_eq!(result, "/spec/containers/0/a~1b~0c");
+        assert_eq!(Path::root().to_json_pointer(), "");
+    }
+
+    #[test]
+    fn test_render_json_path() {
+        // arrange
+        let path = Path::root()
+            .with_key("spec")
+            .with_key("containers")
+            .with_index(0)
+            .with_key("app.kubernetes.io/name")
+            .with_key("it's")
+            .with_selector("*")
+            .with_key("_image2");
+
+        // act
+        let result = path.to_json_path();
+
+        // assert
+        assert_eq!(
+            result,
+            "$.spec.containers[0]['app.kubernetes.io/name']['it\\'s'][*]._image2"
+        );
+        assert_eq!(Path::root().to_json_path(), "$");
+    }
+
+    #[test]
+    fn test_parse_json_pointer() {
+        // arrange
+        let pointer = "/spec/containers/0/a~1b~0c/01/";
+
+        // act
+        let result = Path::from_json_pointer(pointer).unwrap();
+
+        // assert
+        assert_eq!(
+            result,
+            Path::root()
+                .with_key("spec")
+                .with_key("containers")
+                .with_index(0)
+                .with_key("a/b~c")
+                .with_key("01")
+                .with_key("")
+        );
+        assert_eq!(Path::from_json_pointer("").unwrap(), Path::root());
+        assert_eq!(
+            Path::from_json_pointer(&result.to_json_pointer()).unwrap(),
+            result
+        );
+    }
+
+    #[test]
+    fn test_parse_json_pointer_errors() {
+        // arrange
+        let pointers = vec![("spec", 1), ("/spec/a~2", 8), ("/a~", 3)];
+
+        for (pointer, expected_column) in pointers {
+            // act
+            let result = Path::from_json_pointer(pointer);
+
+            // assert
+            match result {
+                Err(Error::Parse { format, column, .. }) => {
+                    assert_eq!(format, "JSON Pointer");
+                    assert_eq!(column, Some(expected_column), "{}", pointer);
+                }
+                other => panic!("expected a parse error for {}, got {:?}", pointer, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_serialize_as_string() {
+        // arrange
+        let path = Path::root().with_key("a.b").with_index(1);
+
+        // act
+        let serialized = serde_json::to_string(&path).unwrap();
+        let deserialized: Path = serde_json::from_str(&serialized).unwrap();
+
+        // assert
+        assert_eq!(serialized, "\"a\\\\.b[1]\"");
+        assert_eq!(deserialized, path);
+    }
+}
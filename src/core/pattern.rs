@@ -0,0 +1,258 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::path::{Path, PathSegment};
+
+/// A pattern, that matches paths segment by segment.
+///
+/// It is written like a path: keys are joined with `.`, and indexes and selectors are wrapped in `[]`. On top of that:
+/// * `*` inside a key or a `[]` segment matches any number of characters, e.g. `metadata.*Timestamp`.
+/// * `[*]` matches any item of an array or collection, e.g. `items[*].status`.
+/// * `**` matches any number of segments, including none, e.g. `**.generation`.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+#[serde(into = "String", from = "String")]
+pub struct PathPattern {
+    text: String,
+    segments: Vec<PatternSegment>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+enum PatternSegment {
+    /// Matches any number of segments
+    AnyDepth,
+    /// Matches a key
+    Key(Glob),
+    /// Matches an index or a selector
+    Item(Glob),
+}
+
+/// A text with `*` wildcards, stored as the literal parts between the wildcards
+#[derive(PartialEq, Eq, Debug, Clone)]
+struct Glob {
+    parts: Vec<String>,
+}
+
+impl PathPattern {
+    /// Parses a pattern. It uses the escaping of `Path::parse`, and every `*` is a wildcard.
+    pub fn parse(text: &str) -> PathPattern {
+        let segments = Path::parse(text)
+            .segments()
+            .iter()
+            .map(|segment| match segment {
+                PathSegment::Key(key) if key == "**" => PatternSegment::AnyDepth,
+                PathSegment::Key(key) => PatternSegment::Key(Glob::new(key)),
+                PathSegment::Index(index) => PatternSegment::Item(Glob::new(&index.to_string())),
+                PathSegment::Selector(selector) => PatternSegment::Item(Glob::new(selector)),
+            })
+            .collect();
+
+        PathPattern {
+            text: text.to_owned(),
+            segments,
+        }
+    }
+
+    /// Tells if the pattern matches the whole path
+    pub fn matches(&self, path: &Path) -> bool {
+        matches_segments(&self.segments, path.segments(), Reach::Exact)
+    }
+
+    /// Tells if the pattern matches the path or one of its parents, so the path is inside a matching subtree
+    pub fn covers(&self, path: &Path) -> bool {
+        matches_segments(&self.segments, path.segments(), Reach::Covers)
+    }
+
+    /// Tells if the pattern matches the path or may match paths under it, so the path is on the way to a matching subtree
+    pub fn leads_through(&self, path: &Path) -> bool {
+        matches_segments(&self.segments, path.segments(), Reach::LeadsThrough)
+    }
+}
+
+/// Tells how the pattern and the path have to line up for a match
+#[derive(PartialEq, Clone, Copy)]
+enum Reach {
+    /// Both end at the same segment
+    Exact,
+    /// The path may go on after the pattern ends
+    Covers,
+    /// The pattern may go on after the path ends
+    LeadsThrough,
+}
+
+fn matches_segments(pattern: &[PatternSegment], path: &[PathSegment], reach: Reach) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty() || reach == Reach::Covers,
+        Some(_) if path.is_empty() && reach == Reach::LeadsThrough => true,
+        Some((PatternSegment::AnyDepth, rest)) => {
+            (0..=path.len()).any(|skipped| matches_segments(rest, &path[skipped..], reach))
+        }
+        Some((segment, rest)) => path.split_first().is_some_and(|(first, path_rest)| {
+            matches_segment(segment, first) && matches_segments(rest, path_rest, reach)
+        }),
+    }
+}
+
+fn matches_segment(pattern: &PatternSegment, segment: &PathSegment) -> bool {
+    match (pattern, segment) {
+        (PatternSegment::Key(glob), PathSegment::Key(key)) => glob.matches(key),
+        (PatternSegment::Item(glob), PathSegment::Index(index)) => glob.matches(&index.to_string()),
+        (PatternSegment::Item(glob), PathSegment::Selector(selector)) => glob.matches(selector),
+        _ => false,
+    }
+}
+
+impl Glob {
+    fn new(text: &str) -> Glob {
+        Glob {
+            parts: text.split('*').map(str::to_owned).collect(),
+        }
+    }
+
+    fn matches(&self, text: &str) -> bool {
+        let (first, rest) = self.parts.split_first().expect("split returns a part");
+        let Some((last, middle)) = rest.split_last() else {
+            return text == first;
+        };
+
+        let Some(mut remainder) = text.strip_prefix(first.as_str()) else {
+            return false;
+        };
+
+        for part in middle {
+            match remainder.find(part.as_str()) {
+                Some(position) => remainder = &remainder[position + part.len()..],
+                None => return false,
+            }
+        }
+
+        remainder.len() >= last.len() && remainder.ends_with(last.as_str())
+    }
+}
+
+impl fmt::Display for PathPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+impl From<&str> for PathPattern {
+    fn from(text: &str) -> PathPattern {
+        PathPattern::parse(text)
+    }
+}
+
+impl From<String> for PathPattern {
+    fn from(text: String) -> PathPattern {
+        PathPattern::parse(&text)
+    }
+}
+
+impl From<PathPattern> for String {
+    fn from(pattern: PathPattern) -> String {
+        pattern.text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::path::Path;
+
+    use super::PathPattern;
+
+    #[test]
+    fn test_matches_key_wildcards() {
+        // arrange
+        let pattern = PathPattern::parse("metadata.*Timestamp");
+
+        // act & assert
+        assert!(pattern.matches(&Path::parse("metadata.creationTimestamp")));
+        assert!(pattern.matches(&Path::parse("metadata.Timestamp")));
+        assert!(!pattern.matches(&Path::parse("metadata.creationTimestamps")));
+        assert!(!pattern.matches(&Path::parse("metadata")));
+        assert!(!pattern.matches(&Path::parse("spec.metadata.creationTimestamp")));
+    }
+
+    #[test]
+    fn test_matches_items() {
+        // arrange
+        let pattern = PathPattern::parse("items[*].status");
+
+        // act & assert
+        assert!(pattern.matches(&Path::parse("items[0].status")));
+        assert!(pattern.matches(&Path::parse("items[id=42].status")));
+        assert!(!pattern.matches(&Path::parse("items.status")));
+        assert!(!pattern.matches(&Path::parse("items[0].status.phase")));
+        assert!(PathPattern::parse("items[1]").matches(&Path::parse("items[1]")));
+        assert!(!PathPattern::parse("items[1]").matches(&Path::parse("items[10]")));
+    }
+
+    #[test]
+    fn test_matches_any_depth() {
+        // arrange
+        let pattern = PathPattern::parse("**.generation");
+
+        // act & assert
+        assert!(pattern.matches(&Path::parse("generation")));
+        assert!(pattern.matches(&Path::parse("metadata.generation")));
+        assert!(pattern.matches(&Path::parse("items[3].metadata.generation")));
+        assert!(!pattern.matches(&Path::parse("metadata.generations")));
+        assert!(PathPattern::parse("spec.**.image").matches(&Path::parse("spec.image")));
+        assert!(PathPattern::parse("**").matches(&Path::root()));
+    }
+
+    #[test]
+    fn test_covers() {
+        // arrange
+        let pattern = PathPattern::parse("spec.containers[*]");
+
+        // act & assert
+        assert!(pattern.covers(&Path::parse("spec.containers[0]")));
+        assert!(pattern.covers(&Path::parse("spec.containers[0].image")));
+        assert!(!pattern.covers(&Path::parse("spec.containers")));
+        assert!(!pattern.covers(&Path::parse("spec")));
+        assert!(PathPattern::parse("**").covers(&Path::parse("a.b")));
+    }
+
+    #[test]
+    fn test_leads_through() {
+        // arrange
+        let pattern = PathPattern::parse("spec.containers[*].image");
+
+        // act & assert
+        assert!(pattern.leads_through(&Path::root()));
+        assert!(pattern.leads_through(&Path::parse("spec")));
+        assert!(pattern.leads_through(&Path::parse("spec.containers[3]")));
+        assert!(!pattern.leads_through(&Path::parse("metadata")));
+        assert!(!pattern.leads_through(&Path::parse("spec.containers[3].name")));
+        assert!(PathPattern::parse("**.version").leads_through(&Path::parse("a.b")));
+    }
+
+    #[test]
+    fn test_matches_escaped_keys() {
+        // arrange
+        let pattern = PathPattern::parse("labels.app\\.kubernetes\\.io/*");
+
+        // act & assert
+        assert!(pattern.matches(
+            &Path::root()
+                .with_key("labels")
+                .with_key("app.kubernetes.io/name")
+        ));
+        assert!(!pattern.matches(&Path::parse("labels.app.kubernetes.io/name")));
+    }
+
+    #[test]
+    fn test_serialize_as_string() {
+        // arrange
+        let pattern = PathPattern::parse("items[*].status");
+
+        // act
+        let serialized = serde_json::to_string(&pattern).unwrap();
+        let deserialized: PathPattern = serde_json::from_str(&serialized).unwrap();
+
+        // assert
+        assert_eq!(serialized, "\"items[*].status\"");
+        assert_eq!(deserialized, pattern);
+    }
+}
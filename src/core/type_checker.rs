@@ -0,0 +1,468 @@
+/// Collects differences between the types of 2 data sets.
+/// Stores `TypeDiff` values
+///
+/// 1. We iterate through object `a` and if a field is present in `b` as well, only then do we take action
+///     1. We construct a new path by adding the currently checked fields key to the path in our checker object. That's how we handle the keys of nested objects. If the config skips the path, we move on to the next field.
+///     2. If `a` and `b` are both objects we recursively start the process over for the nested objects.
+///     3. If both fields are arrays and the user has specified, that arrays should be in the same order, we iterate through the arrays and recursively repeat the checking for each item. If the user hasn't specified the option, this part is pointless.
+///     4. If the types of the fields don't match, we add the difference to our `diffs` vector.
+use crate::core::{
+    diff_types::{Checker, CheckingData, DiffCollection, TypeDiff},
+    node::{Node, NodeObject},
+    path::Path,
+    record,
+};
+
+impl<'a, A: Node, B: Node> Checker<TypeDiff> for CheckingData<'a, TypeDiff, A, B> {
+    fn check(&mut self) {
+        for (a_key, a_value) in self.a.entries() {
+            let key = self.key.with_key(&a_key);
+            if self.working_context.config.skips(&key) {
+                continue;
+            }
+
+            if let Some(b_value) = self.b.get(&a_key) {
+                self.find_type_diffs_in_values(&key, a_value, b_value);
+            }
+        }
+    }
+
+    fn check_and_get(&mut self) -> &DiffCollection<TypeDiff> {
+        self.check();
+        &self.diffs
+    }
+
+    fn diffs(&self) -> &Vec<TypeDiff> {
+        self.diffs.diffs()
+    }
+}
+
+impl<'a, A: Node, B: Node> CheckingData<'a, TypeDiff, A, B> {
+    fn find_type_diffs_in_values(&mut self, key_in: &Path, a: &A, b: &B) {
+        if let (Some(a_object), Some(b_object)) = (a.as_object(), b.as_object()) {
+            self.find_type_diffs_in_objects(key_in, a_object, b_object);
+        }
+
+        if let (true, Some(a_array), Some(b_array)) = (
+            self.working_context.config.array_same_order,
+            a.as_array(),
+            b.as_array(),
+        ) {
+            if a_array.len() == b_array.len() {
+                self.find_type_diffs_in_arrays(key_in, a_array, b_array);
+            }
+        }
+
+        let a_type = a.value_type();
+        let b_type = b.value_type();
+
+        if a_type != b_type && self.working_context.config.reports(key_in) {
+            let config = &self.working_context.config;
+            self.diffs.push(
+                TypeDiff::new(key_in.clone(), a_type.to_string(), b_type.to_string())
+                    .with_json(record(config, a), record(config, b)),
+            );
+        }
+    }
+
+    fn find_type_diffs_in_objects(&mut self, key_in: &Path, a: &A::Object, b: &B::Object) {
+        let mut type_checker: CheckingData<TypeDiff, A, B> =
+            CheckingData::new(key_in.clone(), a, b, self.working_context);
+
+        type_checker.check();
+        self.diffs.concatenate(&mut type_checker.diffs);
+    }
+
+    fn find_type_diffs_in_arrays(&mut self, key_in: &Path, a: &[A], b: &[B]) {
+        for (i, a_item) in a.iter().enumerate() {
+            let key = key_in.with_index(i);
+            if !self.working_context.config.skips(&key) {
+                self.find_type_diffs_in_values(&key, a_item, &b[i]);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod json_tests {
+    use serde_json::json;
+
+    use crate::core::diff_types::{Checker, Config, TypeDiff, WorkingContext, WorkingFile};
+
+    use crate::json::diff_types::CheckingData;
+
+    const FILE_NAME_A: &str = "a.json";
+    const FILE_NAME_B: &str = "b.json";
+
+    #[test]
+    fn test_find_type_diffs_no_array_same_order() {
+        // arrange
+        let a = json!({
+            "a_string_b_int": "a_string_b_int",
+            "both_string": "both_string",
+            "array_3_a_string_b_int": [
+                "string",
+                "string2",
+                "string3",
+                "string4",
+                8,
+                true
+            ],
+            "nested": {
+                "a_bool_b_string": true,
+                "both_number": 4,
+                "array_3_a_int_b_bool": [
+                    "string",
+                    "string2",
+                    "string3",
+                    6,
+                    8,
+                    true
+                ],
+            }
+        });
+        let b = json!({
+            "a_string_b_int": 2,
+            "both_string": "both_string",
+            "array_3_a_string_b_int": [
+                "other_string",
+                "other_string2",
+                "other_string3",
+                5,
+                1,
+                false
+            ],
+            "nested": {
+                "a_bool_b_string": "a_bool_b_string",
+                "both_number": 1,
+                "array_3_a_int_b_bool": [
+                "other_string",
+                "other_string2",
+                "other_string3",
+                false,
+                2,
+                false
+            ],
+            }
+        });
+
+        let expected = vec![
+            TypeDiff::new(
+                "a_string_b_int".to_owned(),
+                "string".to_owned(),
+                "number".to_owned(),
+            ),
+            TypeDiff::new(
+                "nested.a_bool_b_string".to_owned(),
+                "bool".to_owned(),
+                "string".to_owned(),
+            ),
+        ];
+
+        let working_context = create_test_working_context(false);
+        let mut type_checker = CheckingData::new(
+            "",
+            a.as_object().unwrap(),
+            b.as_object().unwrap(),
+            &working_context,
+        );
+
+        // act
+        type_checker.check();
+
+        // assert
+        assert_array(&expected, type_checker.diffs());
+    }
+
+    #[test]
+    fn test_find_type_diffs_array_same_order() {
+        // arrange
+        let a = json!({
+            "a_string_b_int": "a_string_b_int",
+            "both_string": "both_string",
+            "array_3_a_string_b_int": [
+                "string",
+                "string2",
+                "string3",
+                "string4",
+                8,
+                true
+            ],
+            "nested": {
+                "a_bool_b_string": true,
+                "both_number": 4,
+                "array_3_a_int_b_bool": [
+                    "string",
+                    "string2",
+                    "string3",
+                    6,
+                    8,
+                    true
+                ],
+            }
+        });
+        let b = json!({
+            "a_string_b_int": 2,
+            "both_string": "both_string",
+            "array_3_a_string_b_int": [
+                "other_string",
+                "other_string2",
+                "other_string3",
+                5,
+                1,
+                false
+            ],
+            "nested": {
+                "a_bool_b_string": "a_bool_b_string",
+                "both_number": 1,
+                "array_3_a_int_b_bool": [
+                "other_string",
+                "other_string2",
+                "other_string3",
+                false,
+                2,
+                false
+            ],
+            }
+        });
+
+        let expected = vec![
+            TypeDiff::new(
+                "a_string_b_int".to_owned(),
+                "string".to_owned(),
+                "number".to_owned(),
+            ),
+            TypeDiff::new(
+                "nested.a_bool_b_string".to_owned(),
+                "bool".to_owned(),
+                "string".to_owned(),
+            ),
+            TypeDiff::new(
+                "array_3_a_string_b_int[3]".to_owned(),
+                "string".to_owned(),
+                "number".to_owned(),
+            ),
+            TypeDiff::new(
+                "nested.array_3_a_int_b_bool[3]".to_owned(),
+                "number".to_owned(),
+                "bool".to_owned(),
+            ),
+        ];
+
+        let working_context = create_test_working_context(true);
+        let mut type_checker = CheckingData::new(
+            "",
+            a.as_object().unwrap(),
+            b.as_object().unwrap(),
+            &working_context,
+        );
+
+        // act
+        type_checker.check();
+
+        // assert
+        assert_array(&expected, type_checker.diffs());
+    }
+
+    // Test utils
+
+    fn create_test_working_context(array_same_order: bool) -> WorkingContext {
+        let config = Config::new(array_same_order);
+        let working_file_a = WorkingFile::new(FILE_NAME_A.to_owned());
+        let working_file_b = WorkingFile::new(FILE_NAME_B.to_owned());
+        WorkingContext::new(working_file_a, working_file_b, config)
+    }
+
+    fn assert_array<T: PartialEq>(expected: &[T], result: &[T]) {
+        assert_eq!(expected.len(), result.len());
+        assert!(expected.iter().all(|item| result.contains(item)));
+    }
+}
+
+#[cfg(test)]
+mod yaml_tests {
+    use serde_yaml::from_str;
+
+    use crate::core::diff_types::{Checker, Config, TypeDiff, WorkingContext, WorkingFile};
+
+    use crate::yaml::diff_types::CheckingData;
+
+    const FILE_NAME_A: &str = "a.json";
+    const FILE_NAME_B: &str = "b.json";
+
+    #[test]
+    fn test_find_type_diffs_no_array_same_order() {
+        // arrange
+        let a = from_str(
+            r"
+            'a_string_b_int': 'a_string_b_int'
+            'both_string': 'both_string'
+            'array_3_a_string_b_int':
+                - 'string'
+                - 'string2'
+                - 'string3'
+                - 'string4'
+                - 8
+                - true
+            'nested':
+                'a_bool_b_string': true
+                'both_number': 4
+                'array_3_a_int_b_bool':
+                    - 'string'
+                    - 'string2'
+                    - 'string3'
+                    - 6
+                    - 8
+                    - true
+        ",
+        )
+        .unwrap();
+        let b = from_str(
+            r"
+            'a_string_b_int': 2
+            'both_string': 'both_string'
+            'array_3_a_string_b_int':
+                - 'other_string'
+                - 'other_string2'
+                - 'other_string3'
+                - 5,
+                - 1,
+                - false
+            'nested':
+                'a_bool_b_string': 'a_bool_b_string'
+                'both_number': 1
+                'array_3_a_int_b_bool':
+                    - 'other_string'
+                    - 'other_string2'
+                    - 'other_string3'
+                    - false,
+                    - 2,
+                    - false
+        ",
+        )
+        .unwrap();
+
+        let expected = vec![
+            TypeDiff::new(
+                "a_string_b_int".to_owned(),
+                "string".to_owned(),
+                "number".to_owned(),
+            ),
+            TypeDiff::new(
+                "nested.a_bool_b_string".to_owned(),
+                "bool".to_owned(),
+                "string".to_owned(),
+            ),
+        ];
+
+        let working_context = create_test_working_context(false);
+        let mut type_checker = CheckingData::new("", &a, &b, &working_context);
+
+        // act
+        type_checker.check();
+
+        // assert
+        assert_array(&expected, type_checker.diffs());
+    }
+
+    #[test]
+    fn test_find_type_diffs_array_same_order() {
+        // arrange
+        let a = from_str(
+            r"
+            'a_string_b_int': 'a_string_b_int'
+            'both_string': 'both_string'
+            'array_3_a_string_b_int':
+                - 'string'
+                - 'string2'
+                - 'string3'
+                - 'string4'
+                - 8
+                - true
+            'nested':
+                'a_bool_b_string': true
+                'both_number': 4
+                'array_3_a_int_b_bool':
+                    - 'string'
+                    - 'string2'
+                    - 'string3'
+                    - 6
+                    - 8
+                    - true
+        ",
+        )
+        .unwrap();
+        let b = from_str(
+            r"
+            'a_string_b_int': 2
+            'both_string': 'both_string'
+            'array_3_a_string_b_int':
+                - 'other_string'
+                - 'other_string2'
+                - 'other_string3'
+                - 5
+                - 1
+                - false
+            'nested':
+                'a_bool_b_string': 'a_bool_b_string'
+                'both_number': 1
+                'array_3_a_int_b_bool':
+                    - 'other_string'
+                    - 'other_string2'
+                    - 'other_string3'
+                    - false
+                    - 2
+                    - false
+        ",
+        )
+        .unwrap();
+
+        let expected = vec![
+            TypeDiff::new(
+                "a_string_b_int".to_owned(),
+                "string".to_owned(),
+                "number".to_owned(),
+            ),
+            TypeDiff::new(
+                "nested.a_bool_b_string".to_owned(),
+                "bool".to_owned(),
+                "string".to_owned(),
+            ),
+            TypeDiff::new(
+                "array_3_a_string_b_int[3]".to_owned(),
+                "string".to_owned(),
+                "number".to_owned(),
+            ),
+            TypeDiff::new(
+                "nested.array_3_a_int_b_bool[3]".to_owned(),
+                "number".to_owned(),
+                "bool".to_owned(),
+            ),
+        ];
+
+        let working_context = create_test_working_context(true);
+        let mut type_checker = CheckingData::new("", &a, &b, &working_context);
+
+        // act
+        type_checker.check();
+
+        // assert
+        assert_array(&expected, type_checker.diffs());
+    }
+
+    // Test utils
+
+    fn create_test_working_context(array_same_order: bool) -> WorkingContext {
+        let config = Config::new(array_same_order);
+        let working_file_a = WorkingFile::new(FILE_NAME_A.to_owned());
+        let working_file_b = WorkingFile::new(FILE_NAME_B.to_owned());
+        WorkingContext::new(working_file_a, working_file_b, config)
+    }
+
+    fn assert_array<T: PartialEq + std::fmt::Debug>(expected: &Vec<T>, result: &Vec<T>) {
+        println!("expected: {:?}", expected);
+        println!("result: {:?}", result);
+        assert_eq!(expected.len(), result.len());
+        assert!(expected.iter().all(|item| result.contains(item)));
+    }
+}
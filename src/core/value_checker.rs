@@ -0,0 +1,612 @@
+/// Collects differences between the values of 2 data sets.
+/// Stores `ValueDiff` values
+///
+/// 1. We iterate through object `a` and if a field is present in `b` as well, only then do we take action
+///     1. We construct a new path by adding the currently checked fields key to the path in our checker object. That's how we handle the keys of nested objects. If the config skips the path, we move on to the next field.
+///     2. If `a` and `b` are both objects we recursively start the process over for the nested objects.
+///     3. If both fields are arrays and the user has specified, that arrays should be in the same order, we iterate through the arrays and recursively repeat the checking for each item. If the user hasn't specified the option, this part is pointless.
+///     4. If the values of the fields aren't equal, we add the difference to our `diffs` vector.
+use crate::core::{
+    diff_types::{Checker, CheckingData, DiffCollection, ValueDiff},
+    node::{nodes_equal, nodes_equal_at, Node, NodeObject},
+    path::Path,
+    record, record_items,
+};
+
+impl<'a, A: Node, B: Node> Checker<ValueDiff> for CheckingData<'a, ValueDiff, A, B> {
+    fn check(&mut self) {
+        for (a_key, a_value) in self.a.entries() {
+            let key = self.key.with_key(&a_key);
+            if self.working_context.config.skips(&key) {
+                continue;
+            }
+
+            if let Some(b_value) = self.b.get(&a_key) {
+                self.find_value_diffs_in_values(&key, a_value, b_value);
+            }
+        }
+    }
+
+    fn check_and_get(&mut self) -> &DiffCollection<ValueDiff> {
+        self.check();
+        &self.diffs
+    }
+
+    fn diffs(&self) -> &Vec<ValueDiff> {
+        self.diffs.diffs()
+    }
+}
+
+impl<'a, A: Node, B: Node> CheckingData<'a, ValueDiff, A, B> {
+    fn find_value_diffs_in_values(&mut self, key_in: &Path, a: &A, b: &B) {
+        match (a.as_object(), b.as_object(), a.as_array(), b.as_array()) {
+            (Some(a_object), Some(b_object), _, _) => {
+                self.find_value_diffs_in_objects(key_in, a_object, b_object);
+            }
+            (_, _, Some(a_array), Some(b_array))
+                if self.working_context.config.array_same_order
+                    && a_array.len() == b_array.len() =>
+            {
+                self.find_value_diffs_in_arrays(key_in, a_array, b_array);
+            }
+            (_, _, Some(a_array), Some(b_array))
+                if !nodes_equal_at(a, b, key_in, &self.working_context.config) =>
+            {
+                let config = &self.working_context.config;
+                self.diffs.push(
+                    ValueDiff::new(
+                        key_in.clone(),
+                        "Array differences present".to_owned(),
+                        "Array differences present".to_owned(),
+                    )
+                    .with_json(record_items(config, a_array), record_items(config, b_array)),
+                )
+            }
+            (_, _, None, None)
+                if !nodes_equal(a, b) && self.working_context.config.reports(key_in) =>
+            {
+                let config = &self.working_context.config;
+                self.diffs.push(
+                    ValueDiff::new(key_in.clone(), a.to_display_string(), b.to_display_string())
+                        .with_json(record(config, a), record(config, b)),
+                )
+            }
+            _ => {}
+        }
+    }
+
+    fn find_value_diffs_in_objects(&mut self, key_in: &Path, a: &A::Object, b: &B::Object) {
+        let mut value_checker: CheckingData<ValueDiff, A, B> =
+            CheckingData::new(key_in.clone(), a, b, self.working_context);
+
+        value_checker.check();
+        self.diffs.concatenate(&mut value_checker.diffs);
+    }
+
+    fn find_value_diffs_in_arrays(&mut self, key_in: &Path, a: &[A], b: &[B]) {
+        for (index, a_item) in a.iter().enumerate() {
+            let array_key = key_in.with_index(index);
+            if !self.working_context.config.skips(&array_key) {
+                self.find_value_diffs_in_values(&array_key, a_item, &b[index]);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod json_tests {
+    use serde_json::json;
+
+    use crate::core::diff_types::{Checker, Config, ValueDiff, WorkingContext, WorkingFile};
+
+    use crate::json::diff_types::CheckingData;
+
+    const FILE_NAME_A: &str = "a.json";
+    const FILE_NAME_B: &str = "b.json";
+
+    #[test]
+    fn test_find_value_diffs_no_array_same_order() {
+        // arrange
+        let a = json!({
+            "no_diff_string": "no_diff_string",
+            "diff_string": "a",
+            "no_diff_number": "no_diff_number",
+            "diff_number": 1,
+            "no_diff_boolean": true,
+            "diff_boolean": true,
+            "no_diff_array": [
+                1, 2, 3, 4
+            ],
+            "diff_array": [
+                1, 2, 3, 4
+            ],
+            "nested": {
+                "no_diff_string": "no_diff_string",
+                "diff_string": "a",
+                "no_diff_number": "no_diff_number",
+                "diff_number": 1,
+                "no_diff_boolean": true,
+                "diff_boolean": true,
+                "no_diff_array": [
+                    1, 2, 3, 4
+                ],
+                "diff_array": [
+                    1, 2, 3, 4
+                ],
+            },
+        });
+
+        let b = json!({
+            "no_diff_string": "no_diff_string",
+            "diff_string": "b",
+            "no_diff_number": "no_diff_number",
+            "diff_number": 2,
+            "no_diff_boolean": true,
+            "diff_boolean": false,
+            "no_diff_array": [
+                1, 2, 3, 4
+            ],
+            "diff_array": [
+                5, 6, 7, 8
+            ],
+            "nested": {
+                "no_diff_string": "no_diff_string",
+                "diff_string": "b",
+                "no_diff_number": "no_diff_number",
+                "diff_number": 2,
+                "no_diff_boolean": true,
+                "diff_boolean": false,
+                "no_diff_array": [
+                    1, 2, 3, 4
+                ],
+                "diff_array": [
+                    5, 6, 7, 8
+                ],
+            },
+        });
+
+        let expected = vec![
+            ValueDiff::new("diff_string".to_owned(), "a".to_owned(), "b".to_owned()),
+            ValueDiff::new("diff_number".to_owned(), "1".to_owned(), "2".to_owned()),
+            ValueDiff::new(
+                "diff_boolean".to_owned(),
+                "true".to_owned(),
+                "false".to_owned(),
+            ),
+            ValueDiff::new(
+                "diff_array".to_owned(),
+                "Array differences present".to_owned(),
+                "Array differences present".to_owned(),
+            ),
+            ValueDiff::new(
+                "nested.diff_string".to_owned(),
+                "a".to_owned(),
+                "b".to_owned(),
+            ),
+            ValueDiff::new(
+                "nested.diff_number".to_owned(),
+                "1".to_owned(),
+                "2".to_owned(),
+            ),
+            ValueDiff::new(
+                "nested.diff_boolean".to_owned(),
+                "true".to_owned(),
+                "false".to_owned(),
+            ),
+            ValueDiff::new(
+                "nested.diff_array".to_owned(),
+                "Array differences present".to_owned(),
+                "Array differences present".to_owned(),
+            ),
+        ];
+
+        let working_context = create_test_working_context(false);
+        let mut value_checker = CheckingData::new(
+            "",
+            a.as_object().unwrap(),
+            b.as_object().unwrap(),
+            &working_context,
+        );
+
+        // act
+        value_checker.check();
+
+        // assert
+        assert_array(&expected, value_checker.diffs());
+    }
+
+    #[test]
+    fn test_find_value_diffs_array_same_order() {
+        // arrange
+        let a = json!({
+            "no_diff_string": "no_diff_string",
+            "diff_string": "a",
+            "no_diff_number": "no_diff_number",
+            "diff_number": 1,
+            "no_diff_boolean": true,
+            "diff_boolean": true,
+            "no_diff_array": [
+                1, 2, 3, 4
+            ],
+            "diff_array": [
+                1, 2, 3, 4
+            ],
+            "nested": {
+                "no_diff_string": "no_diff_string",
+                "diff_string": "a",
+                "no_diff_number": "no_diff_number",
+                "diff_number": 1,
+                "no_diff_boolean": true,
+                "diff_boolean": true,
+                "no_diff_array": [
+                    1, 2, 3, 4
+                ],
+                "diff_array": [
+                    1, 2, 3, 4
+                ],
+            },
+        });
+
+        let b = json!({
+            "no_diff_string": "no_diff_string",
+            "diff_string": "b",
+            "no_diff_number": "no_diff_number",
+            "diff_number": 2,
+            "no_diff_boolean": true,
+            "diff_boolean": false,
+            "no_diff_array": [
+                1, 2, 3, 4
+            ],
+            "diff_array": [
+                1, 2, 8, 4
+            ],
+            "nested": {
+                "no_diff_string": "no_diff_string",
+                "diff_string": "b",
+                "no_diff_number": "no_diff_number",
+                "diff_number": 2,
+                "no_diff_boolean": true,
+                "diff_boolean": false,
+                "no_diff_array": [
+                    1, 2, 3, 4
+                ],
+                "diff_array": [
+                    1, 2, 8, 4
+                ],
+            },
+        });
+
+        let expected = vec![
+            ValueDiff::new("diff_string".to_owned(), "a".to_owned(), "b".to_owned()),
+            ValueDiff::new("diff_number".to_owned(), "1".to_owned(), "2".to_owned()),
+            ValueDiff::new(
+                "diff_boolean".to_owned(),
+                "true".to_owned(),
+                "false".to_owned(),
+            ),
+            ValueDiff::new("diff_array[2]".to_owned(), "3".to_owned(), "8".to_owned()),
+            ValueDiff::new(
+                "nested.diff_string".to_owned(),
+                "a".to_owned(),
+                "b".to_owned(),
+            ),
+            ValueDiff::new(
+                "nested.diff_number".to_owned(),
+                "1".to_owned(),
+                "2".to_owned(),
+            ),
+            ValueDiff::new(
+                "nested.diff_boolean".to_owned(),
+                "true".to_owned(),
+                "false".to_owned(),
+            ),
+            ValueDiff::new(
+                "nested.diff_array[2]".to_owned(),
+                "3".to_owned(),
+                "8".to_owned(),
+            ),
+        ];
+
+        let working_context = create_test_working_context(true);
+        let mut value_checker = CheckingData::new(
+            "",
+            a.as_object().unwrap(),
+            b.as_object().unwrap(),
+            &working_context,
+        );
+
+        // act
+        value_checker.check();
+
+        // assert
+        assert_array(&expected, value_checker.diffs());
+    }
+
+    // Test utils
+
+    fn create_test_working_context(array_same_order: bool) -> WorkingContext {
+        let config = Config::new(array_same_order);
+        let working_file_a = WorkingFile::new(FILE_NAME_A.to_owned());
+        let working_file_b = WorkingFile::new(FILE_NAME_B.to_owned());
+        WorkingContext::new(working_file_a, working_file_b, config)
+    }
+
+    fn assert_array<T: PartialEq>(expected: &[T], result: &[T]) {
+        assert_eq!(expected.len(), result.len());
+        assert!(expected.iter().all(|item| result.contains(item)));
+    }
+}
+
+#[cfg(test)]
+mod yaml_tests {
+    use serde_yaml::from_str;
+
+    use crate::core::diff_types::{Checker, Config, ValueDiff, WorkingContext, WorkingFile};
+
+    use crate::yaml::diff_types::CheckingData;
+
+    const FILE_NAME_A: &str = "a.json";
+    const FILE_NAME_B: &str = "b.json";
+
+    #[test]
+    fn test_find_value_diffs_no_array_same_order() {
+        // arrange
+        let a = from_str(
+            r"
+            'no_diff_string': 'no_diff_string'
+            'diff_string': 'a'
+            'no_diff_number': 'no_diff_number'
+            'diff_number': 1
+            'no_diff_boolean': true
+            'diff_boolean': true
+            'no_diff_array':
+                - 1
+                - 2
+                - 3
+                - 4
+            'diff_array':
+                - 1
+                - 2
+                - 3
+                - 4
+            'nested':
+                'no_diff_string': 'no_diff_string'
+                'diff_string': 'a'
+                'no_diff_number': 'no_diff_number'
+                'diff_number': 1
+                'no_diff_boolean': true
+                'diff_boolean': true
+                'no_diff_array':
+                    - 1
+                    - 2
+                    - 3
+                    - 4
+                'diff_array':
+                    - 1
+                    - 2
+                    - 3
+                    - 4
+        ",
+        )
+        .unwrap();
+
+        let b = from_str(
+            r"
+            'no_diff_string': 'no_diff_string'
+            'diff_string': 'b'
+            'no_diff_number': 'no_diff_number'
+            'diff_number': 2
+            'no_diff_boolean': true
+            'diff_boolean': false
+            'no_diff_array':
+                - 1
+                - 2
+                - 3
+                - 4
+            'diff_array':
+                - 5
+                - 6
+                - 7
+                - 8
+            'nested':
+                'no_diff_string': 'no_diff_string'
+                'diff_string': 'b'
+                'no_diff_number': 'no_diff_number'
+                'diff_number': 2
+                'no_diff_boolean': true
+                'diff_boolean': false
+                'no_diff_array':
+                    - 1
+                    - 2
+                    - 3
+                    - 4
+                'diff_array':
+                    - 5
+                    - 6
+                    - 7
+                    - 8
+        ",
+        )
+        .unwrap();
+
+        let expected = vec![
+            ValueDiff::new("diff_string".to_owned(), "a".to_owned(), "b".to_owned()),
+            ValueDiff::new("diff_number".to_owned(), "1".to_owned(), "2".to_owned()),
+            ValueDiff::new(
+                "diff_boolean".to_owned(),
+                "true".to_owned(),
+                "false".to_owned(),
+            ),
+            ValueDiff::new(
+                "diff_array".to_owned(),
+                "Array differences present".to_owned(),
+                "Array differences present".to_owned(),
+            ),
+            ValueDiff::new(
+                "nested.diff_string".to_owned(),
+                "a".to_owned(),
+                "b".to_owned(),
+            ),
+            ValueDiff::new(
+                "nested.diff_number".to_owned(),
+                "1".to_owned(),
+                "2".to_owned(),
+            ),
+            ValueDiff::new(
+                "nested.diff_boolean".to_owned(),
+                "true".to_owned(),
+                "false".to_owned(),
+            ),
+            ValueDiff::new(
+                "nested.diff_array".to_owned(),
+                "Array differences present".to_owned(),
+                "Array differences present".to_owned(),
+            ),
+        ];
+
+        let working_context = create_test_working_context(false);
+        let mut value_checker = CheckingData::new("", &a, &b, &working_context);
+
+        // act
+        value_checker.check();
+
+        // assert
+        assert_array(&expected, value_checker.diffs());
+    }
+
+    #[test]
+    fn test_find_value_diffs_array_same_order() {
+        // arrange
+        let a = from_str(
+            r"
+            'no_diff_string': 'no_diff_string'
+            'diff_string': 'a'
+            'no_diff_number': 'no_diff_number'
+            'diff_number': 1
+            'no_diff_boolean': true
+            'diff_boolean': true
+            'no_diff_array':
+                - 1
+                - 2
+                - 3
+                - 4
+            'diff_array':
+                - 1
+                - 2
+                - 3
+                - 4
+            'nested':
+                'no_diff_string': 'no_diff_string'
+                'diff_string': 'a'
+                'no_diff_number': 'no_diff_number'
+                'diff_number': 1
+                'no_diff_boolean': true
+                'diff_boolean': true
+                'no_diff_array':
+                    - 1
+                    - 2
+                    - 3
+                    - 4
+                'diff_array':
+                    - 1
+                    - 2
+                    - 3
+                    - 4
+        ",
+        )
+        .unwrap();
+
+        let b = from_str(
+            r"
+            'no_diff_string': 'no_diff_string'
+            'diff_string': 'b'
+            'no_diff_number': 'no_diff_number'
+            'diff_number': 2
+            'no_diff_boolean': true
+            'diff_boolean': false
+            'no_diff_array':
+                - 1
+                - 2
+                - 3
+                - 4
+            'diff_array':
+                - 1
+                - 2
+                - 8
+                - 4
+            'nested':
+                'no_diff_string': 'no_diff_string'
+                'diff_string': 'b'
+                'no_diff_number': 'no_diff_number'
+                'diff_number': 2
+                'no_diff_boolean': true
+                'diff_boolean': false
+                'no_diff_array':
+                    - 1
+                    - 2
+                    - 3
+                    - 4
+                'diff_array':
+                    - 1
+                    - 2
+                    - 8
+                    - 4
+        ",
+        )
+        .unwrap();
+
+        let expected = vec![
+            ValueDiff::new("diff_string".to_owned(), "a".to_owned(), "b".to_owned()),
+            ValueDiff::new("diff_number".to_owned(), "1".to_owned(), "2".to_owned()),
+            ValueDiff::new(
+                "diff_boolean".to_owned(),
+                "true".to_owned(),
+                "false".to_owned(),
+            ),
+            ValueDiff::new("diff_array[2]".to_owned(), "3".to_owned(), "8".to_owned()),
+            ValueDiff::new(
+                "nested.diff_string".to_owned(),
+                "a".to_owned(),
+                "b".to_owned(),
+            ),
+            ValueDiff::new(
+                "nested.diff_number".to_owned(),
+                "1".to_owned(),
+                "2".to_owned(),
+            ),
+            ValueDiff::new(
+                "nested.diff_boolean".to_owned(),
+                "true".to_owned(),
+                "false".to_owned(),
+            ),
+            ValueDiff::new(
+                "nested.diff_array[2]".to_owned(),
+                "3".to_owned(),
+                "8".to_owned(),
+            ),
+        ];
+
+        let working_context = create_test_working_context(true);
+        let mut value_checker = CheckingData::new("", &a, &b, &working_context);
+
+        // act
+        value_checker.check();
+
+        // assert
+        assert_array(&expected, value_checker.diffs());
+    }
+
+    // Test utils
+
+    fn create_test_working_context(array_same_order: bool) -> WorkingContext {
+        let config = Config::new(array_same_order);
+        let working_file_a = WorkingFile::new(FILE_NAME_A.to_owned());
+        let working_file_b = WorkingFile::new(FILE_NAME_B.to_owned());
+        WorkingContext::new(working_file_a, working_file_b, config)
+    }
+
+    fn assert_array<T: PartialEq>(expected: &[T], result: &[T]) {
+        assert_eq!(expected.len(), result.len());
+        assert!(expected.iter().all(|item| result.contains(item)));
+    }
+}
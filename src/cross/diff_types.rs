@@ -0,0 +1,7 @@
+use crate::core::diff_types::CheckingData;
+
+/// Holds the data required to run a difference check on JSON data against YAML data
+pub type JsonYamlCheckingData<'a, T> = CheckingData<'a, T, serde_json::Value, serde_yaml::Value>;
+
+/// Holds the data required to run a difference check on YAML data against JSON data
+pub type YamlJsonCheckingData<'a, T> = CheckingData<'a, T, serde_yaml::Value, serde_json::Value>;
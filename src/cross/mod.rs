@@ -0,0 +1,233 @@
+use serde_json::{Map, Value};
+use serde_yaml::Mapping;
+
+use crate::core::{
+    comparison::compare,
+    diff_types::{ComparisionResult, DiffKinds, WorkingContext},
+};
+
+pub mod diff_types;
+
+/// Runs the difference checks selected in `diff_kinds` on a JSON object against a YAML mapping
+pub fn compare_json_to_yaml(
+    a: &Map<String, Value>,
+    b: &Mapping,
+    working_context: &WorkingContext,
+    diff_kinds: DiffKinds,
+) -> ComparisionResult {
+    compare::<serde_json::Value, serde_yaml::Value>(a, b, working_context, diff_kinds)
+}
+
+/// Runs the difference checks selected in `diff_kinds` on a YAML mapping against a JSON object
+pub fn compare_yaml_to_json(
+    a: &Mapping,
+    b: &Map<String, Value>,
+    working_context: &WorkingContext,
+    diff_kinds: DiffKinds,
+) -> ComparisionResult {
+    compare::<serde_yaml::Value, serde_json::Value>(a, b, working_context, diff_kinds)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use serde_yaml::{from_str, Mapping};
+
+    use crate::core::diff_types::{
+        ArrayDiff, ArrayDiffDesc, Config, DiffKinds, KeyDiff, TypeDiff, ValueDiff, WorkingContext,
+        WorkingFile,
+    };
+
+    use super::{compare_json_to_yaml, compare_yaml_to_json};
+
+    const FILE_NAME_A: &str = "a.json";
+    const FILE_NAME_B: &str = "b.yaml";
+
+    #[test]
+    fn test_compare_json_to_yaml() {
+        // arrange
+        let a = json!({
+            "a_has": "a_has",
+            "same": "same",
+            "same_number": 4,
+            "diff_value": "a",
+            "diff_type": true,
+            "nested": {
+                "same": [1, 2, 3],
+                "diff_array": [1, 2, 3]
+            }
+        });
+        let b: Mapping = from_str(
+            r"
+            'b_has': 'b_has'
+            'same': 'same'
+            'same_number': 4
+            'diff_value': 'b'
+            'diff_type': 'true'
+            'nested':
+                'same':
+                    - 1
+                    - 2
+                    - 3
+                'diff_array':
+                    - 1
+                    - 2
+                    - 4
+        ",
+        )
+        .unwrap();
+
+        let working_context = create_test_working_context(false);
+
+        // act
+        let (key_diffs, type_diffs, value_diffs, array_diffs) = compare_json_to_yaml(
+            a.as_object().unwrap(),
+            &b,
+            &working_context,
+            DiffKinds::all(),
+        );
+
+        // assert
+        assert_array(
+            &[
+                KeyDiff::new(
+                    "a_has".to_owned(),
+                    FILE_NAME_A.to_owned(),
+                    FILE_NAME_B.to_owned(),
+                ),
+                KeyDiff::new(
+                    "b_has".to_owned(),
+                    FILE_NAME_B.to_owned(),
+                    FILE_NAME_A.to_owned(),
+                ),
+            ],
+            &key_diffs,
+        );
+        assert_array(
+            &[TypeDiff::new(
+                "diff_type".to_owned(),
+                "bool".to_owned(),
+                "string".to_owned(),
+            )],
+            &type_diffs,
+        );
+        assert_array(
+            &[
+                ValueDiff::new("diff_value".to_owned(), "a".to_owned(), "b".to_owned()),
+                ValueDiff::new("diff_type".to_owned(), "true".to_owned(), "true".to_owned()),
+                ValueDiff::new(
+                    "nested.diff_array".to_owned(),
+                    "Array differences present".to_owned(),
+                    "Array differences present".to_owned(),
+                ),
+            ],
+            &value_diffs,
+        );
+        assert_array(
+            &[
+                ArrayDiff::new(
+                    "nested.diff_array".to_owned(),
+                    ArrayDiffDesc::AHas,
+                    "3".to_owned(),
+                ),
+                ArrayDiff::new(
+                    "nested.diff_array".to_owned(),
+                    ArrayDiffDesc::BMisses,
+                    "3".to_owned(),
+                ),
+                ArrayDiff::new(
+                    "nested.diff_array".to_owned(),
+                    ArrayDiffDesc::BHas,
+                    "4".to_owned(),
+                ),
+                ArrayDiff::new(
+                    "nested.diff_array".to_owned(),
+                    ArrayDiffDesc::AMisses,
+                    "4".to_owned(),
+                ),
+            ],
+            &array_diffs,
+        );
+    }
+
+    #[test]
+    fn test_compare_yaml_to_json_no_diffs() {
+        // arrange
+        let a: Mapping = from_str(
+            r"
+            'string': 'string'
+            'number': 1.5
+            'nested':
+                'null': null
+                'array':
+                    - 'a'
+                    - 'b'
+        ",
+        )
+        .unwrap();
+        let b = json!({
+            "string": "string",
+            "number": 1.5,
+            "nested": {
+                "null": null,
+                "array": ["a", "b"]
+            }
+        });
+
+        let working_context = create_test_working_context(true);
+
+        // act
+        let (key_diffs, type_diffs, value_diffs, array_diffs) = compare_yaml_to_json(
+            &a,
+            b.as_object().unwrap(),
+            &working_context,
+            DiffKinds::all(),
+        );
+
+        // assert
+        assert!(key_diffs.is_empty());
+        assert!(type_diffs.is_empty());
+        assert!(value_diffs.is_empty());
+        assert!(array_diffs.is_empty());
+    }
+
+    #[test]
+    fn test_compare_keys_starting_with_bang() {
+        // arrange
+        let json = json!({ "!important": 1, "x": { "!!str y": 2 } });
+        let yaml: Mapping = from_str("\"!important\": 1\nx:\n  \"!!str y\": 2\n").unwrap();
+        let working_context = create_test_working_context(true);
+
+        // act
+        let json_to_yaml = compare_json_to_yaml(
+            json.as_object().unwrap(),
+            &yaml,
+            &working_context,
+            DiffKinds::all(),
+        );
+        let yaml_to_json = compare_yaml_to_json(
+            &yaml,
+            json.as_object().unwrap(),
+            &working_context,
+            DiffKinds::all(),
+        );
+
+        // assert
+        assert_eq!((vec![], vec![], vec![], vec![]), json_to_yaml);
+        assert_eq!((vec![], vec![], vec![], vec![]), yaml_to_json);
+    }
+
+    // Test utils
+
+    fn create_test_working_context(array_same_order: bool) -> WorkingContext {
+        let config = Config::new(array_same_order);
+        let working_file_a = WorkingFile::new(FILE_NAME_A.to_owned());
+        let working_file_b = WorkingFile::new(FILE_NAME_B.to_owned());
+        WorkingContext::new(working_file_a, working_file_b, config)
+    }
+
+    fn assert_array<T: PartialEq>(expected: &[T], result: &[T]) {
+        assert_eq!(expected.len(), result.len());
+        assert!(expected.iter().all(|item| result.contains(item)));
+    }
+}
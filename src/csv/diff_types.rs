@@ -0,0 +1,39 @@
+use std::collections::BTreeMap;
+
+/// Describes how a table should be read in
+#[derive(Debug, Clone)]
+pub struct TableConfig {
+    /// The byte separating the fields, e.g. `b','` for CSV or `b'\t'` for TSV
+    pub delimiter: u8,
+    /// The columns, that together identify a row. At least one is needed.
+    pub key_columns: Vec<String>,
+}
+
+impl TableConfig {
+    pub fn new(delimiter: u8, key_columns: Vec<String>) -> TableConfig {
+        TableConfig {
+            delimiter,
+            key_columns,
+        }
+    }
+
+    /// Config for comma separated data keyed by the given columns
+    pub fn csv(key_columns: Vec<String>) -> TableConfig {
+        TableConfig::new(b',', key_columns)
+    }
+
+    /// Config for tab separated data keyed by the given columns
+    pub fn tsv(key_columns: Vec<String>) -> TableConfig {
+        TableConfig::new(b'\t', key_columns)
+    }
+}
+
+/// A read-in table, with its rows keyed by the values of the key columns
+#[derive(Debug, PartialEq, Clone)]
+pub struct Table {
+    /// The column names in the order of the header
+    pub columns: Vec<String>,
+    /// The rows keyed by their identifier, e.g. `id="42"` or `id="42",region="eu"` for a composite key. The key values are written as JSON strings.
+    /// Each row maps the column names to the cells.
+    pub rows: BTreeMap<String, BTreeMap<String, String>>,
+}
@@ -0,0 +1,458 @@
+//! CSV and TSV tables are compared row by row, with the rows identified by one or more key columns:
+//! * A row, that only one of the tables has, is a `KeyDiff` with a path like `rows[id="42"]`. The key values are written as JSON strings, so a `,` or `=` inside them can't be mistaken for the separators.
+//! * A cell, that changed in a row both tables have, is a `ValueDiff` with a path like `rows[id="42"].price`.
+//! * A column, that only one of the headers has, is a `KeyDiff` with a path like `rows[*].price`. Its cells aren't compared.
+//!
+//! Cells are compared as strings, since CSV has no types.
+use std::collections::{BTreeMap, HashSet};
+use std::fs::File;
+use std::io::Read;
+
+use serde_json::Value;
+
+use crate::core::{
+    diff_types::{
+        ComparisionResult, Config, DiffCollection, DiffKinds, KeyDiff, ValueDiff, WorkingContext,
+    },
+    error::{Error, Result},
+    path::Path,
+};
+
+use self::diff_types::{Table, TableConfig};
+
+pub mod diff_types;
+
+/// Name used in the errors of data read from a reader
+const READER_NAME: &str = "CSV input";
+
+/// Reads in a csv or tsv file
+///
+/// # Errors
+/// Returns an error if the file cannot be read, it isn't valid CSV, two columns have the same name, no key columns are given, a key column is missing or two rows have the same key.
+pub fn read_csv_file(file_path: &str, config: &TableConfig) -> Result<Table> {
+    let file = File::open(file_path).map_err(|error| Error::io(file_path, error))?;
+    read_table(file, file_path, config)
+}
+
+/// Reads in csv or tsv data from a string
+///
+/// # Errors
+/// Returns an error if the content isn't valid CSV, two columns have the same name, no key columns are given, a key column is missing or two rows have the same key.
+pub fn read_csv_str(content: &str, config: &TableConfig) -> Result<Table> {
+    read_csv_slice(content.as_bytes(), config)
+}
+
+/// Reads in csv or tsv data from bytes
+///
+/// # Errors
+/// Returns an error if the content isn't valid CSV, two columns have the same name, no key columns are given, a key column is missing or two rows have the same key.
+pub fn read_csv_slice(content: &[u8], config: &TableConfig) -> Result<Table> {
+    read_table(content, READER_NAME, config)
+}
+
+/// Reads in csv or tsv data from any reader, like stdin or a network stream.
+/// The data is read record by record.
+///
+/// # Errors
+/// Returns an error if the reader fails, the content isn't valid CSV, two columns have the same name, no key columns are given, a key column is missing or two rows have the same key.
+pub fn read_csv_reader<R: Read>(reader: R, config: &TableConfig) -> Result<Table> {
+    read_table(reader, READER_NAME, config)
+}
+
+/// Runs the difference checks selected in `diff_kinds` on table `a` against table `b`.
+/// Only `KeyDiff` and `ValueDiff` values are produced, since the cells have no types and the tables have no arrays.
+pub fn compare(
+    a: &Table,
+    b: &Table,
+    working_context: &WorkingContext,
+    diff_kinds: DiffKinds,
+) -> ComparisionResult {
+    let mut key_diffs: DiffCollection<KeyDiff> = DiffCollection::new();
+    let mut value_diffs: DiffCollection<ValueDiff> = DiffCollection::new();
+
+    let file_a = &working_context.file_a.name;
+    let file_b = &working_context.file_b.name;
+    let config = &working_context.config;
+    let rows_path = Path::root().with_key("rows");
+
+    if diff_kinds.keys {
+        for column in a
+            .columns
+            .iter()
+            .filter(|column| !b.columns.contains(column))
+        {
+            let key = rows_path.with_selector("*").with_key(column);
+            if !config.skips(&key) && config.reports(&key) {
+                key_diffs.push(KeyDiff::new(key, file_a.clone(), file_b.clone()));
+            }
+        }
+        for column in b
+            .columns
+            .iter()
+            .filter(|column| !a.columns.contains(column))
+        {
+            let key = rows_path.with_selector("*").with_key(column);
+            if !config.skips(&key) && config.reports(&key) {
+                key_diffs.push(KeyDiff::new(key, file_b.clone(), file_a.clone()));
+            }
+        }
+    }
+
+    for (row_key, a_row) in &a.rows {
+        let row_path = rows_path.with_selector(row_key);
+        if config.skips(&row_path) {
+            continue;
+        }
+
+        let Some(b_row) = b.rows.get(row_key) else {
+            if diff_kinds.keys && reports_missing_row(config, &row_path, a_row) {
+                key_diffs.push(
+                    KeyDiff::new(row_path, file_a.clone(), file_b.clone())
+                        .with_json(record_row(config, a_row)),
+                );
+            }
+            continue;
+        };
+
+        if !diff_kinds.values {
+            continue;
+        }
+
+        for (column, a_cell) in a_row {
+            let key = row_path.with_key(column);
+            match b_row.get(column) {
+                Some(b_cell) if a_cell != b_cell && !config.skips(&key) && config.reports(&key) => {
+                    value_diffs.push(
+                        ValueDiff::new(key, a_cell.clone(), b_cell.clone()).with_json(
+                            config.record_values.then(|| Value::from(a_cell.as_str())),
+                            config.record_values.then(|| Value::from(b_cell.as_str())),
+                        ),
+                    )
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if diff_kinds.keys {
+        for (row_key, b_row) in b
+            .rows
+            .iter()
+            .filter(|(row_key, _)| !a.rows.contains_key(*row_key))
+        {
+            let key = rows_path.with_selector(row_key);
+            if reports_missing_row(config, &key, b_row) {
+                key_diffs.push(
+                    KeyDiff::new(key, file_b.clone(), file_a.clone())
+                        .with_json(record_row(config, b_row)),
+                );
+            }
+        }
+    }
+
+    (
+        key_diffs.into_diffs(),
+        vec![],
+        value_diffs.into_diffs(),
+        vec![],
+    )
+}
+
+/// Tells if a row only one of the tables has is reported: it's inside an included subtree, or some of its cells are
+fn reports_missing_row(config: &Config, row_path: &Path, row: &BTreeMap<String, String>) -> bool {
+    !config.skips(row_path)
+        && (config.reports(row_path)
+            || row.keys().any(|column| {
+                let key = row_path.with_key(column);
+                !config.skips(&key) && config.reports(&key)
+            }))
+}
+
+/// Returns the row as a JSON object of strings, if the config asks for recording the values in the differences
+fn record_row(config: &Config, row: &BTreeMap<String, String>) -> Option<Value> {
+    config.record_values.then(|| {
+        Value::Object(
+            row.iter()
+                .map(|(column, cell)| (column.clone(), Value::from(cell.as_str())))
+                .collect(),
+        )
+    })
+}
+
+fn read_table<R: Read>(reader: R, name: &str, config: &TableConfig) -> Result<Table> {
+    if config.key_columns.is_empty() {
+        return Err(Error::Parse {
+            format: "CSV",
+            message: "no key columns are given to identify the rows".to_owned(),
+            line: None,
+            column: None,
+        });
+    }
+
+    let mut reader = ::csv::ReaderBuilder::new()
+        .delimiter(config.delimiter)
+        .from_reader(reader);
+
+    let columns: Vec<String> = reader
+        .headers()
+        .map_err(|error| from_csv(error, name))?
+        .iter()
+        .map(str::to_owned)
+        .collect();
+
+    let mut seen_columns = HashSet::new();
+    if let Some(column) = columns.iter().find(|column| !seen_columns.insert(*column)) {
+        return Err(Error::Parse {
+            format: "CSV",
+            message: format!("duplicate column `{}` in the header", column),
+            line: Some(1),
+            column: None,
+        });
+    }
+
+    let key_indexes = config
+        .key_columns
+        .iter()
+        .map(|key_column| {
+            columns
+                .iter()
+                .position(|column| column == key_column)
+                .ok_or_else(|| Error::Parse {
+                    format: "CSV",
+                    message: format!("key column `{}` is missing from the header", key_column),
+                    line: Some(1),
+                    column: None,
+                })
+        })
+        .collect::<Result<Vec<usize>>>()?;
+
+    let mut rows = BTreeMap::new();
+    let mut seen_keys = HashSet::new();
+
+    for record in reader.records() {
+        let record = record.map_err(|error| from_csv(error, name))?;
+
+        let row_key = config
+            .key_columns
+            .iter()
+            .zip(&key_indexes)
+            .map(|(key_column, index)| format!("{}={}", key_column, Value::from(&record[*index])))
+            .collect::<Vec<String>>()
+            .join(",");
+
+        if !seen_keys.insert(row_key.clone()) {
+            return Err(Error::Parse {
+                format: "CSV",
+                message: format!("duplicate row key `{}`", row_key),
+                line: record.position().map(|position| position.line() as usize),
+                column: None,
+            });
+        }
+
+        let cells = columns
+            .iter()
+            .cloned()
+            .zip(record.iter().map(str::to_owned))
+            .collect();
+        rows.insert(row_key, cells);
+    }
+
+    Ok(Table { columns, rows })
+}
+
+fn from_csv(error: ::csv::Error, name: &str) -> Error {
+    let line = error.position().map(|position| position.line() as usize);
+    let message = error.to_string();
+
+    match error.into_kind() {
+        ::csv::ErrorKind::Io(source) => Error::io(name, source),
+        _ => Error::Parse {
+            format: "CSV",
+            message,
+            line,
+            column: None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::core::{
+        diff_types::{Config, DiffKinds, KeyDiff, ValueDiff, WorkingContext, WorkingFile},
+        error::Error,
+    };
+
+    use super::{compare, diff_types::TableConfig, read_csv_str};
+
+    const FILE_NAME_A: &str = "a.csv";
+    const FILE_NAME_B: &str = "b.csv";
+
+    #[test]
+    fn test_read_csv_str() {
+        // act
+        let table = read_csv_str(
+            "id\tregion\tprice\n1\teu\t10\n1\tus\t12\n",
+            &TableConfig::tsv(vec!["id".to_owned(), "region".to_owned()]),
+        )
+        .unwrap();
+
+        // assert
+        assert_eq!(vec!["id", "region", "price"], table.columns);
+        assert_eq!(
+            vec![r#"id="1",region="eu""#, r#"id="1",region="us""#],
+            table.rows.keys().collect::<Vec<&String>>()
+        );
+        assert_eq!("12", table.rows[r#"id="1",region="us""#]["price"]);
+    }
+
+    #[test]
+    fn test_read_csv_str_separators_in_keys() {
+        // act
+        let table = read_csv_str(
+            "id,region,price
+\"1,region=eu\",us,10
+1,\"eu,region=us\",12
+",
+            &TableConfig::csv(vec!["id".to_owned(), "region".to_owned()]),
+        )
+        .unwrap();
+
+        // assert
+        assert_eq!(
+            vec![
+                r#"id="1",region="eu,region=us""#,
+                r#"id="1,region=eu",region="us""#
+            ],
+            table.rows.keys().collect::<Vec<&String>>()
+        );
+    }
+
+    #[test]
+    fn test_read_csv_str_errors() {
+        let config = TableConfig::csv(vec!["id".to_owned()]);
+
+        assert!(matches!(
+            read_csv_str("name,price\na,1\n", &config),
+            Err(Error::Parse { line: Some(1), .. })
+        ));
+        assert!(matches!(
+            read_csv_str("id,price\n1,1\n2,2\n1,3\n", &config),
+            Err(Error::Parse { line: Some(4), .. })
+        ));
+        assert!(matches!(
+            read_csv_str("id,price\n1,1\n2\n", &config),
+            Err(Error::Parse { line: Some(3), .. })
+        ));
+        assert!(matches!(
+            read_csv_str("id,price,price\n1,1,2\n", &config),
+            Err(Error::Parse { line: Some(1), .. })
+        ));
+        assert!(matches!(
+            read_csv_str("id,price\n1,1\n", &TableConfig::csv(vec![])),
+            Err(Error::Parse { line: None, .. })
+        ));
+    }
+
+    #[test]
+    fn test_compare() {
+        // arrange
+        let config = TableConfig::csv(vec!["id".to_owned()]);
+        let a = read_csv_str(
+            "id,name,price,discount\n1,apple,10,0\n2,pear,12,1\n42,plum,5,0\n",
+            &config,
+        )
+        .unwrap();
+        let b = read_csv_str(
+            "id,name,price,stock\n1,apple,10,3\n42,plum,6,7\n43,fig,9,1\n",
+            &config,
+        )
+        .unwrap();
+
+        let working_context = WorkingContext::new(
+            WorkingFile::new(FILE_NAME_A.to_owned()),
+            WorkingFile::new(FILE_NAME_B.to_owned()),
+            Config::new(false),
+        );
+
+        // act
+        let (key_diffs, type_diffs, value_diffs, array_diffs) =
+            compare(&a, &b, &working_context, DiffKinds::all());
+
+        // assert
+        assert_eq!(
+            vec![
+                KeyDiff::new(
+                    "rows[*].discount".to_owned(),
+                    FILE_NAME_A.to_owned(),
+                    FILE_NAME_B.to_owned()
+                ),
+                KeyDiff::new(
+                    "rows[*].stock".to_owned(),
+                    FILE_NAME_B.to_owned(),
+                    FILE_NAME_A.to_owned()
+                ),
+                KeyDiff::new(
+                    r#"rows[id="2"]"#.to_owned(),
+                    FILE_NAME_A.to_owned(),
+                    FILE_NAME_B.to_owned()
+                ),
+                KeyDiff::new(
+                    r#"rows[id="43"]"#.to_owned(),
+                    FILE_NAME_B.to_owned(),
+                    FILE_NAME_A.to_owned()
+                ),
+            ],
+            key_diffs
+        );
+        assert!(type_diffs.is_empty());
+        assert_eq!(
+            vec![ValueDiff::new(
+                r#"rows[id="42"].price"#.to_owned(),
+                "5".to_owned(),
+                "6".to_owned()
+            )],
+            value_diffs
+        );
+        assert!(array_diffs.is_empty());
+    }
+
+    #[test]
+    fn test_compare_records_values() {
+        // arrange
+        let config = TableConfig::csv(vec!["id".to_owned()]);
+        let a = read_csv_str("id,price\n1,10\n2,12\n", &config).unwrap();
+        let b = read_csv_str("id,price\n1,11\n", &config).unwrap();
+
+        let mut working_context = WorkingContext::new(
+            WorkingFile::new(FILE_NAME_A.to_owned()),
+            WorkingFile::new(FILE_NAME_B.to_owned()),
+            Config::new(false),
+        );
+        working_context.config.record_values = true;
+
+        // act
+        let (key_diffs, _, value_diffs, _) = compare(&a, &b, &working_context, DiffKinds::all());
+
+        // assert
+        assert_eq!(
+            vec![KeyDiff::new(
+                r#"rows[id="2"]"#.to_owned(),
+                FILE_NAME_A.to_owned(),
+                FILE_NAME_B.to_owned()
+            )
+            .with_json(Some(json!({ "id": "2", "price": "12" })))],
+            key_diffs
+        );
+        assert_eq!(
+            vec![ValueDiff::new(
+                r#"rows[id="1"].price"#.to_owned(),
+                "10".to_owned(),
+                "11".to_owned()
+            )
+            .with_json(Some(json!("10")), Some(json!("11")))],
+            value_diffs
+        );
+    }
+}
@@ -0,0 +1,512 @@
+use std::{fs, path::Path};
+
+use serde_json::Value;
+
+use crate::{
+    core::{
+        comparison::compare_values,
+        diff_types::{ComparisionResult, Config, DiffKinds, WorkingContext, WorkingFile},
+        error::{Error, Result},
+        node::Node,
+    },
+    ini, json, properties, toml, xml, yaml,
+};
+
+/// The data formats the lib can read
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Format {
+    Json,
+    Json5,
+    Yaml,
+    Toml,
+    Xml,
+    Ini,
+    Properties,
+}
+
+impl Format {
+    /// Determines the format from the extension of the file, if it is a known one
+    pub fn from_extension(file_path: &str) -> Option<Format> {
+        let extension = Path::new(file_path).extension()?.to_str()?;
+
+        match extension.to_lowercase().as_str() {
+            "json" => Some(Format::Json),
+            "json5" | "jsonc" => Some(Format::Json5),
+            "yaml" | "yml" => Some(Format::Yaml),
+            "toml" => Some(Format::Toml),
+            "xml" => Some(Format::Xml),
+            "ini" => Some(Format::Ini),
+            "properties" => Some(Format::Properties),
+            _ => None,
+        }
+    }
+
+    /// Determines the format from the content of the file.
+    /// Content starting with a tag is treated as XML, then content, that parses as JSON, JSON5 or TOML is treated as such, everything else as YAML.
+    /// Content without any TOML keys, like an empty file or one with only comments, isn't taken for TOML, so it's read as an empty YAML document.
+    /// INI and properties files can't be told apart from TOML and YAML by their content, so they are only detected by their extension.
+    pub fn from_content(content: &str) -> Format {
+        detect_content(content).0
+    }
+
+    /// Determines the format from the extension of the file, or if that's not known, from its content
+    pub fn detect(file_path: &str, content: &str) -> Format {
+        Format::from_extension(file_path).unwrap_or_else(|| Format::from_content(content))
+    }
+
+    /// Returns the name of the format, as it appears in the errors
+    pub fn name(&self) -> &'static str {
+        match self {
+            Format::Json => "JSON",
+            Format::Json5 => "JSON5",
+            Format::Yaml => "YAML",
+            Format::Toml => "TOML",
+            Format::Xml => "XML",
+            Format::Ini => "INI",
+            Format::Properties => "properties",
+        }
+    }
+}
+
+/// Determines the format from the content like `Format::from_content`, and returns the document parsed along the way, if there is one
+fn detect_content(content: &str) -> (Format, Option<Document>) {
+    if content.trim_start().starts_with('<') {
+        (Format::Xml, None)
+    } else if let Ok(value) = json::read_json_value_str(content) {
+        (Format::Json, Some(Document::Json(value)))
+    } else if let Ok(value) = json::read_json5_value_str(content) {
+        (Format::Json5, Some(Document::Json5(value)))
+    } else if let Some(table) = toml::read_toml_str(content)
+        .ok()
+        .filter(|table| !table.is_empty())
+    {
+        (
+            Format::Toml,
+            Some(Document::Toml(::toml::Value::Table(table))),
+        )
+    } else {
+        (Format::Yaml, None)
+    }
+}
+
+/// A data set read in from one of the supported formats.
+/// JSON, JSON5 and YAML data sets may have any value as their outermost value, the other formats always have an object.
+pub enum Document {
+    Json(Value),
+    Json5(Value),
+    Yaml(serde_yaml::Value),
+    Toml(::toml::Value),
+    Xml(Value),
+    Ini(Value),
+    Properties(Value),
+}
+
+impl Document {
+    /// Parses the content in the given format
+    pub fn parse(content: &str, format: Format) -> Result<Document> {
+        let document = match format {
+            Format::Json => Document::Json(json::read_json_value_str(content)?),
+            Format::Json5 => Document::Json5(json::read_json5_value_str(content)?),
+            Format::Yaml => Document::Yaml(yaml::read_yaml_value_str(content)?),
+            Format::Toml => Document::Toml(::toml::Value::Table(toml::read_toml_str(content)?)),
+            Format::Xml => Document::Xml(Value::Object(xml::read_xml_str(content)?)),
+            Format::Ini => Document::Ini(Value::Object(ini::read_ini_str(content)?)),
+            Format::Properties => {
+                Document::Properties(Value::Object(properties::read_properties_str(content)?))
+            }
+        };
+        Ok(document)
+    }
+
+    /// Reads in a file and detects its format from the extension or the content.
+    /// YAML is read with `YamlOptions::default()`, so merge keys are kept as they are.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read, it isn't valid UTF-8 or it isn't valid in the detected format.
+    pub fn read_file(file_path: &str) -> Result<Document> {
+        let content = fs::read(file_path).map_err(|error| Error::io(file_path, error))?;
+        let extension_format = Format::from_extension(file_path);
+
+        let content = String::from_utf8(content).map_err(|error| Error::Parse {
+            format: extension_format.map_or("document", |format| format.name()),
+            message: error.to_string(),
+            line: None,
+            column: None,
+        })?;
+
+        match extension_format {
+            Some(format) => Document::parse(&content, format),
+            None => match detect_content(&content) {
+                (_, Some(document)) => Ok(document),
+                (format, None) => Document::parse(&content, format),
+            },
+        }
+    }
+
+    /// Returns the format the document was read from
+    pub fn format(&self) -> Format {
+        match self {
+            Document::Json(_) => Format::Json,
+            Document::Json5(_) => Format::Json5,
+            Document::Yaml(_) => Format::Yaml,
+            Document::Toml(_) => Format::Toml,
+            Document::Xml(_) => Format::Xml,
+            Document::Ini(_) => Format::Ini,
+            Document::Properties(_) => Format::Properties,
+        }
+    }
+}
+
+/// Runs all the difference checks on 2 documents, that may be of different formats.
+/// The repeated elements of 2 XML documents are aligned first, as in `xml::compare`.
+pub fn compare_documents(
+    a: &Document,
+    b: &Document,
+    working_context: &WorkingContext,
+    diff_kinds: DiffKinds,
+) -> ComparisionResult {
+    if let (Document::Xml(a), Document::Xml(b)) = (a, b) {
+        let mut a = a.clone();
+        let mut b = b.clone();
+        xml::align_repeated_values(&mut a, &mut b);
+        return compare_values(&a, &b, working_context, diff_kinds);
+    }
+
+    match a {
+        Document::Json(a)
+        | Document::Json5(a)
+        | Document::Xml(a)
+        | Document::Ini(a)
+        | Document::Properties(a) => compare_to_document(a, b, working_context, diff_kinds),
+        Document::Yaml(a) => compare_to_document(a, b, working_context, diff_kinds),
+        Document::Toml(a) => compare_to_document(a, b, working_context, diff_kinds),
+    }
+}
+
+fn compare_to_document<A: Node>(
+    a: &A,
+    b: &Document,
+    working_context: &WorkingContext,
+    diff_kinds: DiffKinds,
+) -> ComparisionResult {
+    match b {
+        Document::Json(b)
+        | Document::Json5(b)
+        | Document::Xml(b)
+        | Document::Ini(b)
+        | Document::Properties(b) => compare_values(a, b, working_context, diff_kinds),
+        Document::Yaml(b) => compare_values(a, b, working_context, diff_kinds),
+        Document::Toml(b) => compare_values(a, b, working_context, diff_kinds),
+    }
+}
+
+/// Reads in 2 files, detects their formats and runs all the difference checks on them.
+/// The file paths are used as the names of the files in the differences. YAML files are read with `YamlOptions::default()`.
+pub fn compare_files(
+    file_path_a: &str,
+    file_path_b: &str,
+    config: Config,
+) -> Result<ComparisionResult> {
+    let a = Document::read_file(file_path_a)?;
+    let b = Document::read_file(file_path_b)?;
+
+    let working_context = WorkingContext::new(
+        WorkingFile::from_path(file_path_a.to_owned()),
+        WorkingFile::from_path(file_path_b.to_owned()),
+        config,
+    );
+
+    Ok(compare_documents(
+        &a,
+        &b,
+        &working_context,
+        DiffKinds::all(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::core::{
+        diff_types::{
+            Config, DiffKinds, KeyDiff, TypeDiff, ValueDiff, ValueType, WorkingContext,
+            WorkingFile, ROOT_KEY,
+        },
+        error::Error,
+    };
+
+    use super::{compare_documents, compare_files, Document, Format};
+
+    const JSON_FILE: &str = "test_data.json";
+    const YAML_FILE: &str = "test_data.yaml";
+
+    #[test]
+    fn test_format_from_extension() {
+        assert_eq!(Some(Format::Json), Format::from_extension("a/b.json"));
+        assert_eq!(Some(Format::Json5), Format::from_extension("b.json5"));
+        assert_eq!(Some(Format::Json5), Format::from_extension("b.jsonc"));
+        assert_eq!(Some(Format::Yaml), Format::from_extension("a/b.YML"));
+        assert_eq!(Some(Format::Yaml), Format::from_extension("b.yaml"));
+        assert_eq!(Some(Format::Toml), Format::from_extension("Cargo.toml"));
+        assert_eq!(Some(Format::Xml), Format::from_extension("pom.xml"));
+        assert_eq!(Some(Format::Ini), Format::from_extension("setup.ini"));
+        assert_eq!(
+            Some(Format::Properties),
+            Format::from_extension("application.properties")
+        );
+        assert_eq!(None, Format::from_extension("b.txt"));
+        assert_eq!(None, Format::from_extension("b"));
+    }
+
+    #[test]
+    fn test_format_from_content() {
+        assert_eq!(Format::Json, Format::from_content(r#"{ "a": [1, 2] }"#));
+        assert_eq!(
+            Format::Json5,
+            Format::from_content("{ // comment\n a: [1, 2,], }")
+        );
+        assert_eq!(Format::Yaml, Format::from_content("a:\n  - 1\n  - 2\n"));
+        assert_eq!(Format::Toml, Format::from_content("[a]\nb = [1, 2]\n"));
+        assert_eq!(Format::Yaml, Format::from_content(""));
+        assert_eq!(Format::Yaml, Format::from_content(" \n\t\n"));
+        assert_eq!(Format::Yaml, Format::from_content("# comment\n"));
+        assert_eq!(
+            Format::Xml,
+            Format::from_content("\n<?xml version=\"1.0\"?><a/>")
+        );
+    }
+
+    #[test]
+    fn test_compare_files_same_file() {
+        // act
+        let (key_diffs, type_diffs, value_diffs, array_diffs) =
+            compare_files(JSON_FILE, JSON_FILE, Config::new(false)).unwrap();
+
+        // assert
+        assert!(key_diffs.is_empty());
+        assert!(type_diffs.is_empty());
+        assert!(value_diffs.is_empty());
+        assert!(array_diffs.is_empty());
+    }
+
+    #[test]
+    fn test_compare_files_different_formats() {
+        // act
+        let (key_diffs, type_diffs, value_diffs, array_diffs) =
+            compare_files(JSON_FILE, YAML_FILE, Config::new(false)).unwrap();
+
+        // assert
+        assert_eq!(
+            vec![KeyDiff::new(
+                "car".to_owned(),
+                YAML_FILE.to_owned(),
+                JSON_FILE.to_owned()
+            )],
+            key_diffs
+        );
+        assert!(type_diffs.is_empty());
+        assert!(value_diffs.is_empty());
+        assert!(array_diffs.is_empty());
+    }
+
+    #[test]
+    fn test_compare_documents_toml_to_json() {
+        // arrange
+        let a = Document::parse("a = 1\n[b]\nc = \"c\"\n", Format::Toml).unwrap();
+        let b = Document::parse(r#"{ "a": 1, "b": { "c": "d" } }"#, Format::Json).unwrap();
+        let working_context = WorkingContext::new(
+            WorkingFile::new("a".to_owned()),
+            WorkingFile::new("b".to_owned()),
+            Config::new(false),
+        );
+
+        // act
+        let (key_diffs, type_diffs, value_diffs, array_diffs) =
+            compare_documents(&a, &b, &working_context, DiffKinds::all());
+
+        // assert
+        assert!(key_diffs.is_empty());
+        assert!(type_diffs.is_empty());
+        assert_eq!(
+            vec![ValueDiff::new(
+                "b.c".to_owned(),
+                "c".to_owned(),
+                "d".to_owned()
+            )],
+            value_diffs
+        );
+        assert!(array_diffs.is_empty());
+    }
+
+    #[test]
+    fn test_compare_documents_properties_to_yaml() {
+        // arrange
+        let a = Document::parse(
+            "server.port=8080\nspring.application.name=app\nspring.main.lazy=true\n",
+            Format::Properties,
+        )
+        .unwrap();
+        let b = Document::parse(
+            "server:\n  port: 8080\nspring:\n  application:\n    name: other\n  main:\n    lazy: true\n",
+            Format::Yaml,
+        )
+        .unwrap();
+        let working_context = WorkingContext::new(
+            WorkingFile::new("a".to_owned()),
+            WorkingFile::new("b".to_owned()),
+            Config::new(false),
+        );
+
+        // act
+        let (key_diffs, type_diffs, value_diffs, array_diffs) =
+            compare_documents(&a, &b, &working_context, DiffKinds::all());
+
+        // assert
+        assert!(key_diffs.is_empty());
+        assert!(type_diffs.is_empty());
+        assert_eq!(
+            vec![ValueDiff::new(
+                "spring.application.name".to_owned(),
+                "app".to_owned(),
+                "other".to_owned()
+            )],
+            value_diffs
+        );
+        assert!(array_diffs.is_empty());
+    }
+
+    #[test]
+    fn test_compare_files_missing_file() {
+        assert!(matches!(
+            compare_files("missing.json", JSON_FILE, Config::new(false)),
+            Err(Error::Io { .. })
+        ));
+    }
+
+    #[test]
+    fn test_read_file_invalid_utf8() {
+        // arrange
+        let file = TempFile::new("invalid_utf8.json", b"{ \"a\": \"\xff\" }");
+
+        // act
+        let result = Document::read_file(file.path());
+
+        // assert
+        assert!(matches!(result, Err(Error::Parse { format: "JSON", .. })));
+    }
+
+    #[test]
+    fn test_read_file_empty_without_extension() {
+        // arrange
+        let file = TempFile::new("empty", b"\n");
+
+        // act
+        let result = Document::read_file(file.path()).unwrap();
+
+        // assert
+        assert_eq!(Format::Yaml, result.format());
+    }
+
+    #[test]
+    fn test_parse_reports_position() {
+        let result = Document::parse("{\n  \"a\": 1,\n  \"b\" 2\n}", Format::Json);
+
+        assert!(matches!(
+            result,
+            Err(Error::Parse {
+                line: Some(3),
+                column: Some(7),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_compare_documents_non_object_roots() {
+        // arrange
+        let a = Document::parse(r#"[{ "id": 1 }, { "id": 2 }]"#, Format::Json).unwrap();
+        let b = Document::parse("- id: 1\n- id: 3\n", Format::Yaml).unwrap();
+        let working_context = WorkingContext::new(
+            WorkingFile::new("a".to_owned()),
+            WorkingFile::new("b".to_owned()),
+            Config::new(true),
+        );
+
+        // act
+        let (key_diffs, type_diffs, value_diffs, array_diffs) =
+            compare_documents(&a, &b, &working_context, DiffKinds::all());
+
+        // assert
+        assert!(key_diffs.is_empty());
+        assert!(type_diffs.is_empty());
+        assert_eq!(
+            vec![ValueDiff::new(
+                "[1].id".to_owned(),
+                "2".to_owned(),
+                "3".to_owned()
+            )],
+            value_diffs
+        );
+        assert!(array_diffs.is_empty());
+    }
+
+    #[test]
+    fn test_compare_documents_scalar_to_object_root() {
+        // arrange
+        let a = Document::parse("just a string", Format::Yaml).unwrap();
+        let b = Document::parse("a = 1", Format::Toml).unwrap();
+        let working_context = WorkingContext::new(
+            WorkingFile::new("a".to_owned()),
+            WorkingFile::new("b".to_owned()),
+            Config::new(false),
+        );
+
+        // act
+        let (_, type_diffs, _, _) = compare_documents(&a, &b, &working_context, DiffKinds::all());
+
+        // assert
+        assert_eq!(
+            vec![TypeDiff::new(
+                ROOT_KEY.to_owned(),
+                ValueType::String.to_string(),
+                ValueType::Object.to_string()
+            )],
+            type_diffs
+        );
+    }
+
+    #[test]
+    fn test_parse_limit_exceeded() {
+        let content = format!("{}{}", "[".repeat(1000), "]".repeat(1000));
+
+        assert!(matches!(
+            Document::parse(&content, Format::Json),
+            Err(Error::LimitExceeded { .. })
+        ));
+    }
+
+    // Test utils
+
+    /// A file in the temp directory, that is removed when it's dropped, even if the test fails.
+    /// Its name holds the process id, so parallel test runs don't share it.
+    struct TempFile(PathBuf);
+
+    impl TempFile {
+        fn new(name: &str, content: &[u8]) -> TempFile {
+            let path = std::env::temp_dir().join(format!("libdtf_{}_{}", std::process::id(), name));
+            std::fs::write(&path, content).unwrap();
+            TempFile(path)
+        }
+
+        fn path(&self) -> &str {
+            self.0.to_str().unwrap()
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+}
@@ -0,0 +1,225 @@
+//! INI files are read into the same nested model as JSON, so they can be checked against any other format:
+//! * Each `[section]` becomes a top-level object. Keys before the first section are top-level fields.
+//! * Dotted keys are nested inside their section, as in the `properties` module.
+//! * Values of `true` and `false` become booleans, values written as plain numbers become numbers, everything else stays a string.
+//! * Keys are separated from values by `=` or `:`. Lines starting with `;` or `#` are comments. Values wrapped in matching quotes are unquoted.
+//! * A later entry of the same key overrides the earlier one and repeated sections are merged.
+use std::fs;
+use std::io::Read;
+
+use serde_json::{Map, Value};
+
+use crate::{
+    core::{
+        comparison,
+        diff_types::{ComparisionResult, DiffKinds, WorkingContext},
+        error::{Error, Result},
+    },
+    properties::{insert_dotted, parse_scalar, LineError},
+};
+
+pub mod diff_types;
+
+/// Name used in the errors of data read from a reader
+const READER_NAME: &str = "INI input";
+
+/// Reads in an ini file
+///
+/// # Errors
+/// Returns an error if the file cannot be read or it isn't valid INI.
+pub fn read_ini_file(file_path: &str) -> Result<Map<String, Value>> {
+    let content = fs::read(file_path).map_err(|error| Error::io(file_path, error))?;
+    read_ini_slice(&content)
+}
+
+/// Reads in ini data from a string
+///
+/// # Errors
+/// Returns an error if the content isn't valid INI, a key conflicts with another one or nests its value deeper than 128 levels.
+pub fn read_ini_str(content: &str) -> Result<Map<String, Value>> {
+    let mut document = Map::new();
+    let mut section: Option<String> = None;
+
+    for (index, line) in content.lines().enumerate() {
+        parse_line(&mut document, &mut section, line.trim())
+            .map_err(|error| error.into_error("INI", index + 1))?;
+    }
+
+    Ok(document)
+}
+
+/// Reads in ini data from bytes
+///
+/// # Errors
+/// Returns an error if the content isn't valid UTF-8 or valid INI.
+pub fn read_ini_slice(content: &[u8]) -> Result<Map<String, Value>> {
+    let content = std::str::from_utf8(content).map_err(|error| Error::Parse {
+        format: "INI",
+        message: error.to_string(),
+        line: None,
+        column: None,
+    })?;
+    read_ini_str(content)
+}
+
+/// Reads in ini data from any reader, like stdin or a network stream
+///
+/// # Errors
+/// Returns an error if the reader fails or the content isn't valid INI.
+pub fn read_ini_reader<R: Read>(mut reader: R) -> Result<Map<String, Value>> {
+    let mut content = vec![];
+    reader
+        .read_to_end(&mut content)
+        .map_err(|error| Error::io(READER_NAME, error))?;
+    read_ini_slice(&content)
+}
+
+/// Runs the difference checks selected in `diff_kinds` on `a` against `b` in a single traversal
+pub fn compare(
+    a: &Map<String, Value>,
+    b: &Map<String, Value>,
+    working_context: &WorkingContext,
+    diff_kinds: DiffKinds,
+) -> ComparisionResult {
+    comparison::compare::<Value, Value>(a, b, working_context, diff_kinds)
+}
+
+fn parse_line(
+    document: &mut Map<String, Value>,
+    section: &mut Option<String>,
+    line: &str,
+) -> std::result::Result<(), LineError> {
+    if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+        return Ok(());
+    }
+
+    if let Some(header) = line.strip_prefix('[') {
+        let name = header
+            .strip_suffix(']')
+            .ok_or_else(|| "section header is missing the closing `]`".to_owned())?
+            .trim();
+
+        if name.is_empty() {
+            return Err("section name is empty".to_owned().into());
+        }
+
+        if !document
+            .entry(name)
+            .or_insert_with(|| Value::Object(Map::new()))
+            .is_object()
+        {
+            return Err(format!("section `{}` conflicts with a key set before", name).into());
+        }
+
+        *section = Some(name.to_owned());
+        return Ok(());
+    }
+
+    let (key, value) = line
+        .split_once(['=', ':'])
+        .ok_or_else(|| "expected `key = value`".to_owned())?;
+    let key = key.trim();
+
+    if key.is_empty() {
+        return Err("key is empty".to_owned().into());
+    }
+
+    let (target, depth) = match section {
+        Some(name) => (
+            document
+                .get_mut(name.as_str())
+                .and_then(Value::as_object_mut)
+                .ok_or_else(|| format!("section `{}` was overwritten by a key", name))?,
+            1,
+        ),
+        None => (document, 0),
+    };
+
+    insert_dotted(target, depth, key, parse_scalar(unquote(value.trim())))
+}
+
+fn unquote(value: &str) -> &str {
+    ['"', '\'']
+        .iter()
+        .find_map(|quote| {
+            value
+                .strip_prefix(*quote)
+                .and_then(|value| value.strip_suffix(*quote))
+        })
+        .unwrap_or(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::core::error::Error;
+
+    use super::read_ini_str;
+
+    #[test]
+    fn test_read_ini_str() {
+        // act
+        let result = read_ini_str(
+            "; comment\n\
+             name = app\n\
+             \n\
+             [database]\n\
+             host = \"db.local\"\n\
+             port: 5432\n\
+             pool.size = 10\n\
+             \n\
+             [logging]\n\
+             # comment\n\
+             enabled = true\n\
+             \n\
+             [database]\n\
+             user = 'admin'\n",
+        )
+        .unwrap();
+
+        // assert
+        assert_eq!(
+            json!({
+                "name": "app",
+                "database": {
+                    "host": "db.local",
+                    "port": 5432,
+                    "pool": { "size": 10 },
+                    "user": "admin"
+                },
+                "logging": { "enabled": true }
+            }),
+            serde_json::Value::Object(result)
+        );
+    }
+
+    #[test]
+    fn test_read_ini_str_errors() {
+        assert!(matches!(
+            read_ini_str("[a\nb = 1\n"),
+            Err(Error::Parse { line: Some(1), .. })
+        ));
+        assert!(matches!(
+            read_ini_str("a = 1\nb\n"),
+            Err(Error::Parse { line: Some(2), .. })
+        ));
+        assert!(matches!(
+            read_ini_str("a = 1\n[a]\n"),
+            Err(Error::Parse { line: Some(2), .. })
+        ));
+    }
+
+    #[test]
+    fn test_read_ini_str_limit_exceeded() {
+        assert!(matches!(
+            read_ini_str(&format!("[a]\n{} = 1\n", vec!["k"; 300000].join("."))),
+            Err(Error::LimitExceeded { format: "INI", .. })
+        ));
+        assert!(read_ini_str(&format!("[a]\n{} = 1\n", vec!["k"; 127].join("."))).is_ok());
+        assert!(matches!(
+            read_ini_str(&format!("[a]\n{} = 1\n", vec!["k"; 128].join("."))),
+            Err(Error::LimitExceeded { .. })
+        ));
+    }
+}
@@ -0,0 +1,313 @@
+//! Reconstructs one of the compared JSON documents from the other one and the differences found between them.
+//!
+//! The differences only hold the values needed for this if they were collected with `Config::record_values` set.
+//!
+//! 1. The type and value differences replace the value at their path with the recorded value of the other side. Array value differences hold the whole arrays, so the arrays get the exact items and order of the other side.
+//! 2. The key differences remove the value at their path if the document has it, or add the recorded value if it doesn't.
+//! 3. The array differences of arrays without a value difference remove the items only the document has and append the items only the other side has.
+use std::collections::{BTreeMap, HashSet};
+
+use serde_json::Value;
+
+use crate::core::{
+    diff_types::{ArrayDiff, ArrayDiffDesc, ComparisionResult},
+    error::{Error, Result},
+    node::node_at,
+    path::Path,
+};
+
+use super::{
+    diff_types::{ApplyDirection, PatchOperation},
+    patch::apply_patch,
+};
+
+/// Applies the differences on `document` and returns the other document of the comparison.
+/// With `ApplyDirection::AToB` the document is `a` and `b` is returned, with `ApplyDirection::BToA` it's the other way around.
+///
+/// # Errors
+/// Returns an error if a difference has no recorded value, or it doesn't fit the document.
+pub fn apply_diffs(
+    document: &Value,
+    diffs: &ComparisionResult,
+    direction: ApplyDirection,
+) -> Result<Value> {
+    let (key_diffs, type_diffs, value_diffs, array_diffs) = diffs;
+    let mut operations = vec![];
+    let mut replaced_paths = HashSet::new();
+
+    let replacements = type_diffs
+        .iter()
+        .map(|diff| (&diff.key, &diff.json1, &diff.json2))
+        .chain(
+            value_diffs
+                .iter()
+                .map(|diff| (&diff.key, &diff.json1, &diff.json2)),
+        );
+
+    for (path, json1, json2) in replacements {
+        if !replaced_paths.insert(path) {
+            continue;
+        }
+
+        let target = match direction {
+            ApplyDirection::AToB => json2,
+            ApplyDirection::BToA => json1,
+        };
+        operations.push(PatchOperation::Replace {
+            path: path.clone(),
+            value: recorded(path, target)?.clone(),
+        });
+    }
+
+    for diff in key_diffs {
+        if node_at(document, &diff.key).is_some() {
+            operations.push(PatchOperation::Remove {
+                path: diff.key.clone(),
+            });
+        } else {
+            operations.push(PatchOperation::Add {
+                path: diff.key.clone(),
+                value: recorded(&diff.key, &diff.json)?.clone(),
+            });
+        }
+    }
+
+    let mut arrays: BTreeMap<&Path, Vec<&ArrayDiff>> = BTreeMap::new();
+    for diff in array_diffs
+        .iter()
+        .filter(|diff| !replaced_paths.contains(&diff.key))
+    {
+        arrays.entry(&diff.key).or_default().push(diff);
+    }
+
+    for (path, diffs) in arrays {
+        operations.push(PatchOperation::Replace {
+            path: path.clone(),
+            value: edit_items(document, path, &diffs, direction)?,
+        });
+    }
+
+    apply_patch(document, &operations)
+}
+
+/// Removes the items only the document has from the array at the path and appends the items only the other side has
+fn edit_items(
+    document: &Value,
+    path: &Path,
+    diffs: &[&ArrayDiff],
+    direction: ApplyDirection,
+) -> Result<Value> {
+    let Some(Value::Array(items)) = node_at(document, path) else {
+        return Err(apply_error(path, "the value is not an array"));
+    };
+    let mut items = items.clone();
+
+    for diff in diffs {
+        let item = recorded(path, &diff.json)?;
+
+        match (direction, diff.descriptor) {
+            (ApplyDirection::AToB, ArrayDiffDesc::AHas)
+            | (ApplyDirection::BToA, ArrayDiffDesc::BHas) => {
+                let Some(position) = items.iter().position(|candidate| candidate == item) else {
+                    return Err(apply_error(path, "the array misses an item to remove"));
+                };
+                items.remove(position);
+            }
+            (ApplyDirection::AToB, ArrayDiffDesc::BHas)
+            | (ApplyDirection::BToA, ArrayDiffDesc::AHas) => items.push(item.clone()),
+            // The misses descriptors mirror the has descriptors of the other side
+            _ => {}
+        }
+    }
+
+    Ok(Value::Array(items))
+}
+
+fn recorded<'a>(path: &Path, json: &'a Option<Value>) -> Result<&'a Value> {
+    json.as_ref().ok_or_else(|| {
+        apply_error(
+            path,
+            "the difference has no recorded value, collect the differences with `Config::record_values` set",
+        )
+    })
+}
+
+fn apply_error(path: &Path, message: &str) -> Error {
+    Error::Patch {
+        path: path.clone(),
+        message: message.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use crate::{
+        core::{
+            diff_types::{ComparisionResult, Config, DiffKinds, WorkingContext, WorkingFile},
+            error::Error,
+        },
+        json::{compare_values, diff_types::ApplyDirection},
+    };
+
+    use super::apply_diffs;
+
+    #[test]
+    fn test_apply_diffs_both_directions() {
+        // arrange
+        let (a, b) = create_test_data();
+
+        for array_same_order in [false, true] {
+            let diffs = compare_values(
+                &a,
+                &b,
+                &create_test_working_context(array_same_order, true),
+                DiffKinds::all(),
+            );
+
+            // act
+            let b_result = apply_diffs(&a, &diffs, ApplyDirection::AToB).unwrap();
+            let a_result = apply_diffs(&b, &diffs, ApplyDirection::BToA).unwrap();
+
+            // assert
+            assert_eq!(b, b_result, "array_same_order: {}", array_same_order);
+            assert_eq!(a, a_result, "array_same_order: {}", array_same_order);
+        }
+    }
+
+    #[test]
+    fn test_apply_stored_diffs() {
+        // arrange
+        let (a, b) = create_test_data();
+        let diffs = compare_values(
+            &a,
+            &b,
+            &create_test_working_context(false, true),
+            DiffKinds::all(),
+        );
+        let stored = serde_json::to_string(&diffs).unwrap();
+
+        // act
+        let diffs: ComparisionResult = serde_json::from_str(&stored).unwrap();
+        let result = apply_diffs(&a, &diffs, ApplyDirection::AToB).unwrap();
+
+        // assert
+        assert_eq!(b, result);
+    }
+
+    #[test]
+    fn test_apply_stored_diffs_empty_key() {
+        // arrange
+        let a = json!({ "": 1 });
+        let b = json!({ "": 2 });
+        let diffs = compare_values(
+            &a,
+            &b,
+            &create_test_working_context(false, true),
+            DiffKinds::all(),
+        );
+        let stored = serde_json::to_string(&diffs).unwrap();
+
+        // act
+        let diffs: ComparisionResult = serde_json::from_str(&stored).unwrap();
+        let result = apply_diffs(&a, &diffs, ApplyDirection::AToB).unwrap();
+
+        // assert
+        assert_eq!(b, result);
+    }
+
+    #[test]
+    fn test_apply_array_diffs() {
+        // arrange
+        let a = json!({ "tags": ["a", "b", "1", "c"] });
+        let b = json!({ "tags": ["b", 1, "c"] });
+        let diffs = compare_values(
+            &a,
+            &b,
+            &create_test_working_context(false, true),
+            DiffKinds::new(true, true, false, true),
+        );
+
+        // act
+        let b_result = apply_diffs(&a, &diffs, ApplyDirection::AToB).unwrap();
+
+        // assert
+        assert_eq!(json!({ "tags": ["b", "c", 1] }), b_result);
+    }
+
+    #[test]
+    fn test_apply_diffs_without_recorded_values() {
+        // arrange
+        let a = json!({ "a": 1 });
+        let b = json!({ "a": 2 });
+        let diffs = compare_values(
+            &a,
+            &b,
+            &create_test_working_context(false, false),
+            DiffKinds::all(),
+        );
+
+        // act
+        let result = apply_diffs(&a, &diffs, ApplyDirection::AToB);
+
+        // assert
+        assert!(matches!(result, Err(Error::Patch { .. })));
+    }
+
+    // Test utils
+
+    fn create_test_data() -> (Value, Value) {
+        let a = json!({
+            "a_has": { "nested": [1, "2"] },
+            "diff_string": "a",
+            "a_string_b_int": "1",
+            "a_object_b_string": { "key": "value" },
+            "a_array_b_null": [1],
+            "diff_array": [1, 2, 3, 4],
+            "diff_length_array": [1, 2, 3],
+            "reordered_array": ["x", "y", "z"],
+            "array_of_objects": [
+                { "a_has": 1, "diff_number": 1.5 },
+                { "both_have": [1, 2] }
+            ],
+            "nested": {
+                "a_has": null,
+                "diff_boolean": true,
+                "diff_array": ["a", "b"]
+            }
+        });
+        let b = json!({
+            "b_has": "b_has",
+            "diff_string": "b",
+            "a_string_b_int": 1,
+            "a_object_b_string": "value",
+            "a_array_b_null": null,
+            "diff_array": [1, 2, 8, 4],
+            "diff_length_array": [1, 2],
+            "reordered_array": ["z", "x", "y"],
+            "array_of_objects": [
+                { "b_has": [true], "diff_number": 2 },
+                { "both_have": [2, 1] }
+            ],
+            "nested": {
+                "b_has": "b_has",
+                "diff_boolean": false,
+                "diff_array": ["b", "c"]
+            }
+        });
+
+        (a, b)
+    }
+
+    fn create_test_working_context(array_same_order: bool, record_values: bool) -> WorkingContext {
+        let mut config = Config::new(array_same_order);
+        config.record_values = record_values;
+
+        WorkingContext::new(
+            WorkingFile::new("a.json".to_owned()),
+            WorkingFile::new("b.json".to_owned()),
+            config,
+        )
+    }
+}
@@ -1,35 +1,138 @@
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 
-use crate::core::diff_types::{Diff, DiffCollection, WorkingContext};
-
-/// Holds the data required to run a difference check
-pub struct CheckingData<'a, T: Diff> {
-    /// Holds the collected differences
-    pub diffs: DiffCollection<T>,
-    /// Holds the key of the field currently checked - empty if it's the outermost object
-    pub key: &'a str,
-    /// One of the 2 objects that should be checked
-    pub a: &'a Map<String, Value>,
-    /// One of the 2 objects that should be checked
-    pub b: &'a Map<String, Value>,
-    /// Holds relevant data for the current run, such as file names, and user configs
-    pub working_context: &'a WorkingContext,
+use crate::core::{
+    diff_types::ValueType,
+    merge::MergeNode,
+    node::{Node, NodeObject},
+    path::{json_pointer, Path, PathSegment},
+};
+
+/// Holds the data required to run a difference check on JSON data
+pub type CheckingData<'a, T> = crate::core::diff_types::CheckingData<'a, T, Value>;
+
+impl MergeNode for Value {
+    fn child_mut(&mut self, segment: &PathSegment) -> Option<&mut Value> {
+        match (self, segment) {
+            (Value::Object(object), PathSegment::Key(key)) => object.get_mut(key),
+            (Value::Array(items), PathSegment::Index(index)) => items.get_mut(*index),
+            _ => None,
+        }
+    }
+
+    fn copy_field(&mut self, key: &str, source: &Value) {
+        let Value::Object(object) = self else {
+            return;
+        };
+
+        match source.get(key) {
+            Some(value) => {
+                object.insert(key.to_owned(), value.clone());
+            }
+            None => {
+                object.remove(key);
+            }
+        }
+    }
+}
+
+/// Tells which way a comparison result is applied on a document
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ApplyDirection {
+    /// The document is `a` of the comparison and `b` is reconstructed
+    AToB,
+    /// The document is `b` of the comparison and `a` is reconstructed
+    BToA,
+}
+
+/// A single operation of an RFC 6902 JSON Patch. The paths are serialized as JSON Pointers.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+    /// Adds `value` at `path`. Inserts it before the item at the index, if the parent is an array.
+    Add {
+        #[serde(with = "json_pointer")]
+        path: Path,
+        value: Value,
+    },
+    /// Removes the value at `path`
+    Remove {
+        #[serde(with = "json_pointer")]
+        path: Path,
+    },
+    /// Replaces the value at `path` with `value`
+    Replace {
+        #[serde(with = "json_pointer")]
+        path: Path,
+        value: Value,
+    },
+    /// Removes the value at `from` and adds it at `path`
+    Move {
+        #[serde(with = "json_pointer")]
+        from: Path,
+        #[serde(with = "json_pointer")]
+        path: Path,
+    },
+}
+
+impl Node for Value {
+    type Object = Map<String, Value>;
+
+    fn value_type(&self) -> ValueType {
+        match self {
+            Value::Null => ValueType::Null,
+            Value::Bool(_) => ValueType::Boolean,
+            Value::Number(_) => ValueType::Number,
+            Value::String(_) => ValueType::String,
+            Value::Array(_) => ValueType::Array,
+            Value::Object(_) => ValueType::Object,
+        }
+    }
+
+    fn as_object(&self) -> Option<&Map<String, Value>> {
+        Value::as_object(self)
+    }
+
+    fn as_array(&self) -> Option<&[Value]> {
+        Value::as_array(self).map(Vec::as_slice)
+    }
+
+    fn to_display_string(&self) -> String {
+        // String values are escaped by default if to_string() is called on them, so if it is a string, we call as_str() first.
+        self.as_str()
+            .map_or_else(|| self.to_string(), |v| v.to_owned())
+    }
 }
 
-impl<'a, T: Diff> CheckingData<'a, T> {
-    pub fn new(
-        key: &'a str,
-        a: &'a Map<String, Value>,
-        b: &'a Map<String, Value>,
-        working_context: &'a WorkingContext,
-    ) -> CheckingData<'a, T> {
-        let diff_collection: DiffCollection<T> = DiffCollection::new();
-        CheckingData {
-            diffs: diff_collection,
-            key,
-            a,
-            b,
-            working_context,
+impl NodeObject<Value> for Map<String, Value> {
+    fn entries(&self) -> Box<dyn Iterator<Item = (Cow<'_, str>, &Value)> + '_> {
+        Box::new(
+            self.iter()
+                .map(|(key, value)| (Cow::from(key.as_str()), value)),
+        )
+    }
+
+    fn get(&self, key: &str) -> Option<&Value> {
+        Map::get(self, key)
+    }
+}
+
+/// An RFC 7386 JSON Merge Patch together with the arrays it replaces as a whole
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct MergePatch {
+    /// The partial document, that is merged into the target. A `null` field removes the field from the target.
+    pub patch: Value,
+    /// The paths of the arrays, that changed in `b`. Merge patches can't express changes of single items, so these arrays are replaced as a whole.
+    pub replaced_arrays: Vec<Path>,
+}
+
+impl MergePatch {
+    pub fn new(patch: Value, replaced_arrays: Vec<Path>) -> MergePatch {
+        MergePatch {
+            patch,
+            replaced_arrays,
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,402 @@
+//! Turns the differences found between 2 JSON values into an RFC 7386 JSON Merge Patch and applies such patches.
+//!
+//! A merge patch is a partial document: the fields it has replace the fields of the target, nested objects are merged recursively and `null` removes a field.
+//! Arrays can't be patched item by item, so a change anywhere inside an array replaces the whole array. The paths of these arrays are listed in `MergePatch::replaced_arrays`.
+use serde_json::{Map, Value};
+
+use crate::core::{
+    diff_types::ComparisionResult,
+    error::{Error, Result},
+    node::node_at,
+    path::{Path, PathSegment},
+};
+
+use super::diff_types::MergePatch;
+
+/// Creates the minimal merge patch, that transforms `a` into `b` from the key, type, value and array differences found between them.
+/// The differences should be collected with `DiffKinds::all()` from the same `a` and `b`, otherwise the patch only covers the collected ones.
+/// If `a` or `b` isn't an object, only replacing the whole document works, so the patch is `b` itself.
+///
+/// # Errors
+/// Returns an error if `b` has a changed field with a `null` value, since merge patches can't set a field to `null`,
+/// or the path of a difference is missing from both values.
+pub fn create_merge_patch(a: &Value, b: &Value, diffs: &ComparisionResult) -> Result<MergePatch> {
+    if !a.is_object() || !b.is_object() {
+        return replace_document(a, b);
+    }
+
+    let (key_diffs, type_diffs, value_diffs, array_diffs) = diffs;
+    let paths = key_diffs
+        .iter()
+        .map(|diff| &diff.key)
+        .chain(type_diffs.iter().map(|diff| &diff.key))
+        .chain(value_diffs.iter().map(|diff| &diff.key))
+        .chain(array_diffs.iter().map(|diff| &diff.key));
+
+    let mut merge_patch = MergePatch::new(Value::Object(Map::new()), vec![]);
+
+    for path in paths {
+        let path = outermost_array(path);
+
+        match (node_at(a, &path), node_at(b, &path)) {
+            (Some(_), None) => set_field(&mut merge_patch.patch, &path, Value::Null)?,
+            (Some(a_value), Some(b_value)) if a_value == b_value => {}
+            (a_value, Some(b_value)) => {
+                if has_null_field(b_value) {
+                    return Err(Error::Patch {
+                        path,
+                        message: "a merge patch can't set a field to null".to_owned(),
+                    });
+                }
+                if let (Some(Value::Array(_)), Value::Array(_)) = (a_value, b_value) {
+                    if !merge_patch.replaced_arrays.contains(&path) {
+                        merge_patch.replaced_arrays.push(path.clone());
+                    }
+                }
+                set_field(&mut merge_patch.patch, &path, b_value.clone())?;
+            }
+            (None, None) => {
+                return Err(Error::Patch {
+                    path,
+                    message: "the value is missing from both sides".to_owned(),
+                })
+            }
+        }
+    }
+
+    Ok(merge_patch)
+}
+
+/// Creates the merge patch, that replaces the whole document `a` with `b`
+fn replace_document(a: &Value, b: &Value) -> Result<MergePatch> {
+    if b.is_object() && has_null_field(b) {
+        return Err(Error::Patch {
+            path: Path::root(),
+            message: "a merge patch can't set a field to null".to_owned(),
+        });
+    }
+
+    let replaced_arrays = match (a, b) {
+        (Value::Array(_), Value::Array(_)) if a != b => vec![Path::root()],
+        _ => vec![],
+    };
+    Ok(MergePatch::new(b.clone(), replaced_arrays))
+}
+
+/// Applies the merge patch on a copy of the document and returns the result
+pub fn apply_merge_patch(document: &Value, patch: &Value) -> Value {
+    let Value::Object(patch_fields) = patch else {
+        return patch.clone();
+    };
+
+    let mut fields = match document {
+        Value::Object(fields) => fields.clone(),
+        _ => Map::new(),
+    };
+
+    for (key, patch_value) in patch_fields {
+        if patch_value.is_null() {
+            fields.remove(key);
+        } else {
+            let merged = apply_merge_patch(fields.get(key).unwrap_or(&Value::Null), patch_value);
+            fields.insert(key.clone(), merged);
+        }
+    }
+
+    Value::Object(fields)
+}
+
+/// Cuts the path before its first index, since the items of an array can only be patched by replacing the whole array
+fn outermost_array(path: &Path) -> Path {
+    let segments = path.segments();
+    let end = segments
+        .iter()
+        .position(|segment| matches!(segment, PathSegment::Index(_)))
+        .unwrap_or(segments.len());
+    Path::new(segments[..end].to_vec())
+}
+
+/// Sets the field at the path of the patch, creating the objects on the way.
+/// If the patch already replaces one of the parents, the field is part of that replacement and is left alone.
+fn set_field(patch: &mut Value, path: &Path, value: Value) -> Result<()> {
+    let mut current = patch;
+
+    for (position, segment) in path.segments().iter().enumerate() {
+        let PathSegment::Key(key) = segment else {
+            return Err(Error::Patch {
+                path: path.clone(),
+                message: "a merge patch can only address object fields".to_owned(),
+            });
+        };
+        let Value::Object(fields) = current else {
+            return Ok(());
+        };
+
+        if position + 1 == path.segments().len() {
+            fields.insert(key.clone(), value);
+            return Ok(());
+        }
+
+        current = fields
+            .entry(key.clone())
+            .or_insert_with(|| Value::Object(Map::new()));
+    }
+
+    *current = value;
+    Ok(())
+}
+
+/// Tells if the value is `null` or has a `null` field in one of its nested objects. Arrays are replaced as a whole, so their items don't count.
+fn has_null_field(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Object(fields) => fields.values().any(has_null_field),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use crate::{
+        core::{
+            diff_types::{Config, DiffKinds, WorkingContext, WorkingFile},
+            error::Error,
+            path::Path,
+        },
+        json::compare_values,
+    };
+
+    use super::{apply_merge_patch, create_merge_patch};
+
+    #[test]
+    fn test_create_merge_patch() {
+        // arrange
+        let a = json!({
+            "name": "web",
+            "replicas": 1,
+            "labels": { "tier": "a", "team": "x" },
+            "ports": [80]
+        });
+        let b = json!({
+            "name": "web",
+            "replicas": 3,
+            "labels": { "tier": "b", "team": "x" },
+            "ports": [80, 443],
+            "image": { "tag": "1.2" }
+        });
+        let diffs = compare_values(
+            &a,
+            &b,
+            &create_test_working_context(false),
+            DiffKinds::all(),
+        );
+
+        // act
+        let result = create_merge_patch(&a, &b, &diffs).unwrap();
+
+        // assert
+        assert_eq!(
+            json!({
+                "replicas": 3,
+                "labels": { "tier": "b" },
+                "ports": [80, 443],
+                "image": { "tag": "1.2" }
+            }),
+            result.patch
+        );
+        assert_eq!(vec![Path::root().with_key("ports")], result.replaced_arrays);
+    }
+
+    #[test]
+    fn test_merge_patch_round_trip() {
+        // arrange
+        let (a, b) = create_test_data();
+
+        for array_same_order in [false, true] {
+            let diffs = compare_values(
+                &a,
+                &b,
+                &create_test_working_context(array_same_order),
+                DiffKinds::all(),
+            );
+
+            // act
+            let merge_patch = create_merge_patch(&a, &b, &diffs).unwrap();
+            let result = apply_merge_patch(&a, &merge_patch.patch);
+
+            // assert
+            assert_eq!(b, result, "array_same_order: {}", array_same_order);
+            assert_eq!(
+                vec![
+                    Path::root().with_key("array_of_objects"),
+                    Path::root().with_key("diff_array"),
+                    Path::root().with_key("diff_length_array"),
+                    Path::root().with_key("nested").with_key("diff_array"),
+                ],
+                sorted(merge_patch.replaced_arrays),
+                "array_same_order: {}",
+                array_same_order
+            );
+        }
+    }
+
+    #[test]
+    fn test_merge_patch_non_object_roots() {
+        // arrange
+        let a = json!([1, 2]);
+        let b = json!({ "a": 1 });
+        let diffs = compare_values(
+            &a,
+            &b,
+            &create_test_working_context(false),
+            DiffKinds::all(),
+        );
+
+        // act
+        let result = create_merge_patch(&a, &b, &diffs).unwrap();
+
+        // assert
+        assert_eq!(b, result.patch);
+        assert!(result.replaced_arrays.is_empty());
+        assert_eq!(b, apply_merge_patch(&a, &result.patch));
+    }
+
+    #[test]
+    fn test_merge_patch_equal_non_object_roots() {
+        for value in [json!(1), json!([1]), json!("a"), json!(null)] {
+            // arrange
+            let diffs = compare_values(
+                &value,
+                &value,
+                &create_test_working_context(false),
+                DiffKinds::all(),
+            );
+
+            // act
+            let result = create_merge_patch(&value, &value, &diffs).unwrap();
+
+            // assert
+            assert_eq!(value, result.patch);
+            assert!(result.replaced_arrays.is_empty());
+            assert_eq!(value, apply_merge_patch(&value, &result.patch));
+        }
+
+        // arrange
+        let a = json!([1, 2]);
+        let b = json!([2]);
+        let diffs = compare_values(
+            &a,
+            &b,
+            &create_test_working_context(false),
+            DiffKinds::all(),
+        );
+
+        // act
+        let result = create_merge_patch(&a, &b, &diffs).unwrap();
+
+        // assert
+        assert_eq!(vec![Path::root()], result.replaced_arrays);
+        assert_eq!(b, apply_merge_patch(&a, &result.patch));
+    }
+
+    #[test]
+    fn test_create_merge_patch_null_value() {
+        // arrange
+        let cases = vec![
+            (json!({ "a": 1 }), json!({ "a": null }), "a"),
+            (json!({ "b": {} }), json!({ "b": { "c": null } }), "b.c"),
+            (json!({}), json!({ "d": { "e": null } }), "d"),
+        ];
+
+        for (a, b, expected_path) in cases {
+            let diffs = compare_values(
+                &a,
+                &b,
+                &create_test_working_context(false),
+                DiffKinds::all(),
+            );
+
+            // act
+            let result = create_merge_patch(&a, &b, &diffs);
+
+            // assert
+            match result {
+                Err(Error::Patch { path, .. }) => assert_eq!(Path::parse(expected_path), path),
+                other => panic!("expected a patch error, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_apply_merge_patch() {
+        // arrange
+        let document = json!({ "a": "b", "c": { "d": "e", "f": "g" } });
+        let patch = json!({ "a": "z", "c": { "f": null }, "h": [1] });
+
+        // act
+        let result = apply_merge_patch(&document, &patch);
+
+        // assert
+        assert_eq!(json!({ "a": "z", "c": { "d": "e" }, "h": [1] }), result);
+    }
+
+    // Test utils
+
+    fn create_test_data() -> (Value, Value) {
+        let a = json!({
+            "a_has": "a_has",
+            "diff_string": "a",
+            "a_string_b_int": "1",
+            "a_object_b_string": { "key": "value" },
+            "a_string_b_object": "value",
+            "diff_array": [1, 2, 3, 4],
+            "diff_length_array": [1, 2, 3],
+            "array_of_objects": [
+                { "a_has": 1, "diff_number": 1 },
+                { "both_have": [1, 2] }
+            ],
+            "same_array": [null, 1],
+            "nested": {
+                "a_has": "a_has",
+                "diff_boolean": true,
+                "diff_array": ["a", "b"]
+            }
+        });
+        let b = json!({
+            "b_has": { "b_has": "b_has" },
+            "diff_string": "b",
+            "a_string_b_int": 1,
+            "a_object_b_string": "value",
+            "a_string_b_object": { "key": "value" },
+            "diff_array": [1, 2, 8, 4],
+            "diff_length_array": [1, 2],
+            "array_of_objects": [
+                { "b_has": 1, "diff_number": 2 },
+                { "both_have": [2, 1] }
+            ],
+            "same_array": [null, 1],
+            "nested": {
+                "b_has": "b_has",
+                "diff_boolean": false,
+                "diff_array": ["b", "c"]
+            }
+        });
+
+        (a, b)
+    }
+
+    fn sorted(mut paths: Vec<Path>) -> Vec<Path> {
+        paths.sort();
+        paths
+    }
+
+    fn create_test_working_context(array_same_order: bool) -> WorkingContext {
+        WorkingContext::new(
+            WorkingFile::new("a.json".to_owned()),
+            WorkingFile::new("b.json".to_owned()),
+            Config::new(array_same_order),
+        )
+    }
+}
@@ -1,30 +1,315 @@
-use std::fs::File;
-use std::io::BufReader;
+use std::fs::{self, File};
+use std::io::{BufReader, Read};
 
-mod array_checker;
+use serde_json::{Map, Value};
+
+use crate::core::{
+    comparison,
+    diff_types::{ComparisionResult, DiffKinds, MergeResult, WorkingContext},
+    error::{Error, Result, DEPTH_LIMIT},
+    node::Node,
+};
+
+pub mod apply;
 pub mod diff_types;
-mod key_checker;
-mod type_checker;
-mod value_checker;
+pub mod merge_patch;
+pub mod patch;
+
+/// Name used in the errors of data read from a reader
+const READER_NAME: &str = "JSON input";
 
 /// Reads in a json file
 ///
 /// # Errors
-/// Panics if the file cannot be opened.
-pub fn read_json_file(
-    file_path: &str,
-) -> serde_json::Result<serde_json::Map<String, serde_json::Value>> {
-    let file =
-        File::open(file_path).unwrap_or_else(|_| panic!("Could not open file {}", file_path));
-    let reader = BufReader::new(file);
-    let result = serde_json::from_reader(reader)?;
-    Ok(result)
+/// Returns an error if the file cannot be read, it isn't valid JSON or its outermost value isn't an object.
+pub fn read_json_file(file_path: &str) -> Result<Map<String, Value>> {
+    let file = File::open(file_path).map_err(|error| Error::io(file_path, error))?;
+    read_json_from(file, file_path)
+}
+
+/// Reads in json data from a string
+///
+/// # Errors
+/// Returns an error if the content isn't valid JSON or its outermost value isn't an object.
+pub fn read_json_str(content: &str) -> Result<Map<String, Value>> {
+    object_from_value(read_json_value_str(content)?)
+}
+
+/// Reads in a json file, that may have any value as its outermost value, e.g. an array of fixtures
+///
+/// # Errors
+/// Returns an error if the file cannot be read or it isn't valid JSON.
+pub fn read_json_value_file(file_path: &str) -> Result<Value> {
+    let file = File::open(file_path).map_err(|error| Error::io(file_path, error))?;
+    serde_json::from_reader(BufReader::new(file)).map_err(|error| {
+        if error.is_io() {
+            Error::io(file_path, error.into())
+        } else {
+            Error::from_json(error)
+        }
+    })
+}
+
+/// Reads in json data, that may have any value as its outermost value, from a string
+///
+/// # Errors
+/// Returns an error if the content isn't valid JSON.
+pub fn read_json_value_str(content: &str) -> Result<Value> {
+    serde_json::from_str(content).map_err(Error::from_json)
+}
+
+/// Reads in json data from bytes
+///
+/// # Errors
+/// Returns an error if the content isn't valid JSON or its outermost value isn't an object.
+pub fn read_json_slice(content: &[u8]) -> Result<Map<String, Value>> {
+    object_from_value(serde_json::from_slice(content).map_err(Error::from_json)?)
+}
+
+/// Reads in json data from any reader, like stdin or a network stream
+///
+/// # Errors
+/// Returns an error if the reader fails, the content isn't valid JSON or its outermost value isn't an object.
+pub fn read_json_reader<R: Read>(reader: R) -> Result<Map<String, Value>> {
+    read_json_from(reader, READER_NAME)
+}
+
+/// Reads in a json5 or jsonc file, like `tsconfig.json` or VS Code settings.
+/// Comments, trailing commas, single-quoted strings, unquoted keys and the rest of JSON5 are accepted.
+///
+/// # Errors
+/// Returns an error if the file cannot be read, it isn't valid JSON5 or its outermost value isn't an object.
+pub fn read_json5_file(file_path: &str) -> Result<Map<String, Value>> {
+    let content = fs::read(file_path).map_err(|error| Error::io(file_path, error))?;
+    read_json5_slice(&content)
+}
+
+/// Reads in json5 or jsonc data from a string
+///
+/// # Errors
+/// Returns an error if the content isn't valid JSON5, it is nested too deep or its outermost value isn't an object.
+pub fn read_json5_str(content: &str) -> Result<Map<String, Value>> {
+    object_from_value(read_json5_value_str(content)?)
+}
+
+/// Reads in json5 or jsonc data, that may have any value as its outermost value, from a string
+///
+/// # Errors
+/// Returns an error if the content isn't valid JSON5 or it is nested too deep.
+pub fn read_json5_value_str(content: &str) -> Result<Value> {
+    check_json5_depth(content)?;
+    json5::from_str(content).map_err(Error::from_json5)
 }
 
-fn format_key(key_in: &str, current_key: &str) -> String {
-    if key_in.is_empty() {
-        current_key.to_owned()
-    } else {
-        format!("{}.{}", key_in, current_key)
+/// Reads in json5 or jsonc data from bytes
+///
+/// # Errors
+/// Returns an error if the content isn't valid UTF-8, valid JSON5 or its outermost value isn't an object.
+pub fn read_json5_slice(content: &[u8]) -> Result<Map<String, Value>> {
+    let content = std::str::from_utf8(content).map_err(|error| Error::Parse {
+        format: "JSON5",
+        message: error.to_string(),
+        line: None,
+        column: None,
+    })?;
+    read_json5_str(content)
+}
+
+/// Reads in json5 or jsonc data from any reader, like stdin or a network stream
+///
+/// # Errors
+/// Returns an error if the reader fails, the content isn't valid JSON5 or its outermost value isn't an object.
+pub fn read_json5_reader<R: Read>(mut reader: R) -> Result<Map<String, Value>> {
+    let mut content = vec![];
+    reader
+        .read_to_end(&mut content)
+        .map_err(|error| Error::io(READER_NAME, error))?;
+    read_json5_slice(&content)
+}
+
+/// Runs the difference checks selected in `diff_kinds` on `a` against `b` in a single traversal
+pub fn compare(
+    a: &Map<String, Value>,
+    b: &Map<String, Value>,
+    working_context: &WorkingContext,
+    diff_kinds: DiffKinds,
+) -> ComparisionResult {
+    comparison::compare::<Value, Value>(a, b, working_context, diff_kinds)
+}
+
+/// Runs the difference checks selected in `diff_kinds` on 2 values of any type, e.g. top-level arrays, in a single traversal.
+/// The differences of the outermost values themselves have `ROOT_KEY` as their key.
+pub fn compare_values(
+    a: &Value,
+    b: &Value,
+    working_context: &WorkingContext,
+    diff_kinds: DiffKinds,
+) -> ComparisionResult {
+    comparison::compare_values(a, b, working_context, diff_kinds)
+}
+
+/// Merges the changes `ours` and `theirs` made to `base`, e.g. a customized config and a new default one.
+/// Changes of only one side are merged automatically. If both sides changed the same field differently, it keeps the value of `ours` and the conflict is returned as a `ConflictDiff`.
+pub fn merge(
+    base: &Value,
+    ours: &Value,
+    theirs: &Value,
+    working_context: &WorkingContext,
+) -> MergeResult<Value> {
+    crate::core::merge::merge(base, ours, theirs, working_context)
+}
+
+/// Returns the object, if it is the outermost value of the data set
+pub(crate) fn object_from_value(value: Value) -> Result<Map<String, Value>> {
+    match value {
+        Value::Object(object) => Ok(object),
+        other => Err(Error::UnsupportedRoot {
+            format: "JSON",
+            found: other.value_type(),
+        }),
+    }
+}
+
+fn read_json_from<R: Read>(reader: R, name: &str) -> Result<Map<String, Value>> {
+    let value = serde_json::from_reader(BufReader::new(reader)).map_err(|error| {
+        if error.is_io() {
+            Error::io(name, error.into())
+        } else {
+            Error::from_json(error)
+        }
+    })?;
+    object_from_value(value)
+}
+
+/// The JSON5 parser is recursive, so the nesting is checked up front to guard against stack overflows.
+/// Brackets inside strings and comments don't count.
+fn check_json5_depth(content: &str) -> Result<()> {
+    let mut depth = 0usize;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' | '\'' => {
+                while let Some(next) = chars.next() {
+                    if next == '\\' {
+                        chars.next();
+                    } else if next == c {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                chars.by_ref().find(|next| *next == '\n');
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                for next in chars.by_ref() {
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+                    previous = next;
+                }
+            }
+            '{' | '[' => {
+                depth += 1;
+                if depth > DEPTH_LIMIT {
+                    return Err(Error::depth_limit("JSON5"));
+                }
+            }
+            '}' | ']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Read};
+
+    use crate::core::error::Error;
+
+    use super::{read_json5_str, read_json_reader, read_json_slice, read_json_str};
+
+    const CONTENT: &str = r#"{ "a": { "b": [1, 2] } }"#;
+
+    #[test]
+    fn test_read_json_str() {
+        let result = read_json_str(CONTENT).unwrap();
+
+        assert_eq!(1, result.len());
+        assert!(result["a"]["b"].is_array());
+    }
+
+    #[test]
+    fn test_read_json_slice() {
+        let result = read_json_slice(CONTENT.as_bytes()).unwrap();
+
+        assert_eq!(read_json_str(CONTENT).unwrap(), result);
+    }
+
+    #[test]
+    fn test_read_json_reader() {
+        let result = read_json_reader(CONTENT.as_bytes()).unwrap();
+
+        assert_eq!(read_json_str(CONTENT).unwrap(), result);
+    }
+
+    #[test]
+    fn test_read_json_reader_failing_reader() {
+        struct FailingReader;
+
+        impl Read for FailingReader {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::new(io::ErrorKind::BrokenPipe, "broken pipe"))
+            }
+        }
+
+        assert!(matches!(
+            read_json_reader(FailingReader),
+            Err(Error::Io { .. })
+        ));
+    }
+
+    #[test]
+    fn test_read_json5_str() {
+        // act
+        let result = read_json5_str(
+            r#"// tsconfig
+            {
+                /* block comment with a { bracket */
+                compilerOptions: {
+                    'target': 'es2020',
+                    "paths": ["src/*", "lib/*",],
+                },
+            }"#,
+        )
+        .unwrap();
+
+        // assert
+        assert_eq!(
+            serde_json::json!({
+                "compilerOptions": { "target": "es2020", "paths": ["src/*", "lib/*"] }
+            }),
+            serde_json::Value::Object(result)
+        );
+    }
+
+    #[test]
+    fn test_read_json5_str_errors() {
+        assert!(matches!(
+            read_json5_str("{\n  a: 1\n  b: 2\n}"),
+            Err(Error::Parse { line: Some(2), .. })
+        ));
+        assert!(matches!(
+            read_json5_str("[1, 2]"),
+            Err(Error::UnsupportedRoot { .. })
+        ));
+        assert!(matches!(
+            read_json5_str(&format!("{{a: {}1{}}}", "[".repeat(200), "]".repeat(200))),
+            Err(Error::LimitExceeded { .. })
+        ));
     }
 }
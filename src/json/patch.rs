@@ -0,0 +1,430 @@
+//! Turns the differences found between 2 JSON values into an RFC 6902 JSON Patch and applies such patches.
+//!
+//! 1. We go through the paths of all the collected differences once.
+//! 2. If the path is only present in `a`, we remove it. If it is only present in `b`, we add the value of `b`.
+//! 3. If both values are arrays, we edit the array of `a` item by item: the items `b` has fewer of are removed, the remaining ones are moved into the order of `b` and the missing ones are added.
+//! 4. Any other pair of differing values is replaced by the value of `b`.
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+use serde_json::Value;
+
+use crate::core::{
+    diff_types::ComparisionResult,
+    error::{Error, Result},
+    node::node_at,
+    path::{Path, PathSegment},
+};
+
+use super::diff_types::PatchOperation;
+
+/// Creates the JSON Patch, that transforms `a` into `b` from the differences found between them.
+/// The differences should be collected with `DiffKinds::all()` from the same `a` and `b`, otherwise the patch only covers the collected ones.
+/// If the arrays were compared without `array_same_order`, the patch still puts their items in the order of `b`.
+///
+/// # Errors
+/// Returns an error if the path of a difference is missing from both values.
+pub fn create_patch(
+    a: &Value,
+    b: &Value,
+    diffs: &ComparisionResult,
+) -> Result<Vec<PatchOperation>> {
+    let (key_diffs, type_diffs, value_diffs, array_diffs) = diffs;
+    let paths = key_diffs
+        .iter()
+        .map(|diff| &diff.key)
+        .chain(type_diffs.iter().map(|diff| &diff.key))
+        .chain(value_diffs.iter().map(|diff| &diff.key))
+        .chain(array_diffs.iter().map(|diff| &diff.key));
+
+    let mut operations = vec![];
+    let mut patched_paths = HashSet::new();
+
+    for path in paths {
+        if !patched_paths.insert(path) {
+            continue;
+        }
+
+        match (node_at(a, path), node_at(b, path)) {
+            (Some(_), None) => operations.push(PatchOperation::Remove { path: path.clone() }),
+            (None, Some(b_value)) => operations.push(PatchOperation::Add {
+                path: path.clone(),
+                value: b_value.clone(),
+            }),
+            (Some(Value::Array(a_items)), Some(Value::Array(b_items))) => {
+                operations.append(&mut edit_array(path, a_items, b_items))
+            }
+            (Some(a_value), Some(b_value)) if a_value != b_value => {
+                operations.push(PatchOperation::Replace {
+                    path: path.clone(),
+                    value: b_value.clone(),
+                })
+            }
+            (Some(_), Some(_)) => {}
+            (None, None) => return Err(patch_error(path, "the value is missing from both sides")),
+        }
+    }
+
+    Ok(operations)
+}
+
+/// Applies the operations of the patch one after the other on a copy of the document and returns the result.
+///
+/// # Errors
+/// Returns an error if an operation refers to a missing value, an index out of bounds or a value is moved into itself.
+pub fn apply_patch(document: &Value, patch: &[PatchOperation]) -> Result<Value> {
+    let mut document = document.clone();
+
+    for operation in patch {
+        match operation {
+            PatchOperation::Add { path, value } => add(&mut document, path, value.clone())?,
+            PatchOperation::Remove { path } => {
+                remove(&mut document, path)?;
+            }
+            PatchOperation::Replace { path, value } => {
+                *value_at_mut(&mut document, path)? = value.clone();
+            }
+            PatchOperation::Move { from, path } => {
+                if path != from && path.starts_with(from) {
+                    return Err(patch_error(path, "a value can't be moved into itself"));
+                }
+                let value = remove(&mut document, from)?;
+                add(&mut document, path, value)?;
+            }
+        }
+    }
+
+    Ok(document)
+}
+
+/// Creates the operations, that turn the items of `a` into the items of `b`
+fn edit_array(path: &Path, a: &[Value], b: &[Value]) -> Vec<PatchOperation> {
+    let mut operations = vec![];
+    let mut unmatched: Vec<&Value> = b.iter().collect();
+    let kept: Vec<bool> = a
+        .iter()
+        .map(
+            |a_item| match unmatched.iter().position(|b_item| *b_item == a_item) {
+                Some(position) => {
+                    unmatched.swap_remove(position);
+                    true
+                }
+                None => false,
+            },
+        )
+        .collect();
+
+    let mut items: Vec<&Value> = a.iter().collect();
+    for index in (0..a.len()).rev().filter(|index| !kept[*index]) {
+        operations.push(PatchOperation::Remove {
+            path: path.with_index(index),
+        });
+        items.remove(index);
+    }
+
+    for (index, b_item) in b.iter().enumerate() {
+        if items.get(index) == Some(&b_item) {
+            continue;
+        }
+
+        match items[index..].iter().position(|item| *item == b_item) {
+            Some(offset) => {
+                operations.push(PatchOperation::Move {
+                    from: path.with_index(index + offset),
+                    path: path.with_index(index),
+                });
+                let item = items.remove(index + offset);
+                items.insert(index, item);
+            }
+            None => {
+                operations.push(PatchOperation::Add {
+                    path: path.with_index(index),
+                    value: b_item.clone(),
+                });
+                items.insert(index, b_item);
+            }
+        }
+    }
+
+    operations
+}
+
+fn value_at_mut<'a>(document: &'a mut Value, path: &Path) -> Result<&'a mut Value> {
+    path.segments()
+        .iter()
+        .try_fold(document, |value, segment| match value {
+            Value::Object(object) => object.get_mut(object_key(segment)?.as_ref()),
+            Value::Array(items) => items.get_mut(array_index(segment)?),
+            _ => None,
+        })
+        .ok_or_else(|| patch_error(path, "the value is missing"))
+}
+
+fn add(document: &mut Value, path: &Path, value: Value) -> Result<()> {
+    let (Some(parent), Some(last)) = (path.parent(), path.last()) else {
+        *document = value;
+        return Ok(());
+    };
+
+    match value_at_mut(document, &parent)? {
+        Value::Object(object) => {
+            if let Some(key) = object_key(last) {
+                object.insert(key.into_owned(), value);
+                return Ok(());
+            }
+        }
+        Value::Array(items) => match (last, array_index(last)) {
+            (PathSegment::Key(key), _) if key == "-" => {
+                items.push(value);
+                return Ok(());
+            }
+            (_, Some(index)) if index <= items.len() => {
+                items.insert(index, value);
+                return Ok(());
+            }
+            _ => {}
+        },
+        _ => {}
+    }
+
+    Err(patch_error(
+        path,
+        "the parent can't hold a value at this path",
+    ))
+}
+
+fn remove(document: &mut Value, path: &Path) -> Result<Value> {
+    let (Some(parent), Some(last)) = (path.parent(), path.last()) else {
+        return Ok(std::mem::take(document));
+    };
+
+    let removed = match value_at_mut(document, &parent)? {
+        Value::Object(object) => object_key(last).and_then(|key| object.remove(key.as_ref())),
+        Value::Array(items) => array_index(last)
+            .filter(|index| *index < items.len())
+            .map(|index| items.remove(index)),
+        _ => None,
+    };
+
+    removed.ok_or_else(|| patch_error(path, "the value is missing"))
+}
+
+/// Returns the key of an object field the segment points to. Indexes are keys too, since JSON Pointers can't tell them apart.
+fn object_key(segment: &PathSegment) -> Option<Cow<'_, str>> {
+    match segment {
+        PathSegment::Key(key) => Some(Cow::from(key.as_str())),
+        PathSegment::Index(index) => Some(Cow::from(index.to_string())),
+        PathSegment::Selector(_) => None,
+    }
+}
+
+fn array_index(segment: &PathSegment) -> Option<usize> {
+    match segment {
+        PathSegment::Index(index) => Some(*index),
+        _ => None,
+    }
+}
+
+fn patch_error(path: &Path, message: &str) -> Error {
+    Error::Patch {
+        path: path.clone(),
+        message: message.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use crate::{
+        core::{
+            diff_types::{Config, DiffKinds, WorkingContext, WorkingFile},
+            error::Error,
+            path::Path,
+        },
+        json::{compare_values, diff_types::PatchOperation},
+    };
+
+    use super::{apply_patch, create_patch};
+
+    #[test]
+    fn test_create_patch() {
+        // arrange
+        let a = json!({ "name": "web", "replicas": 1, "labels": { "tier": "a" } });
+        let b = json!({ "name": "web", "replicas": 3, "ports": [80] });
+        let diffs = compare_values(
+            &a,
+            &b,
+            &create_test_working_context(false),
+            DiffKinds::all(),
+        );
+
+        // act
+        let patch = create_patch(&a, &b, &diffs).unwrap();
+
+        // assert
+        assert_eq!(
+            vec![
+                PatchOperation::Remove {
+                    path: Path::root().with_key("labels")
+                },
+                PatchOperation::Add {
+                    path: Path::root().with_key("ports"),
+                    value: json!([80])
+                },
+                PatchOperation::Replace {
+                    path: Path::root().with_key("replicas"),
+                    value: json!(3)
+                },
+            ],
+            patch
+        );
+    }
+
+    #[test]
+    fn test_patch_round_trip() {
+        // arrange
+        let (a, b) = create_test_data();
+
+        for array_same_order in [false, true] {
+            let diffs = compare_values(
+                &a,
+                &b,
+                &create_test_working_context(array_same_order),
+                DiffKinds::all(),
+            );
+
+            // act
+            let patch = create_patch(&a, &b, &diffs).unwrap();
+            let result = apply_patch(&a, &patch).unwrap();
+
+            // assert
+            assert_eq!(b, result, "array_same_order: {}", array_same_order);
+        }
+    }
+
+    #[test]
+    fn test_patch_round_trip_through_json() {
+        // arrange
+        let (a, b) = create_test_data();
+        let diffs = compare_values(
+            &a,
+            &b,
+            &create_test_working_context(false),
+            DiffKinds::all(),
+        );
+        let patch = create_patch(&a, &b, &diffs).unwrap();
+
+        // act
+        let serialized = serde_json::to_string(&patch).unwrap();
+        let deserialized: Vec<PatchOperation> = serde_json::from_str(&serialized).unwrap();
+        let result = apply_patch(&a, &deserialized).unwrap();
+
+        // assert
+        assert_eq!(b, result);
+    }
+
+    #[test]
+    fn test_patch_array_edit() {
+        // arrange
+        let a = json!([1, 2, 3, 2]);
+        let b = json!([3, 4, 2, 1]);
+        let diffs = compare_values(
+            &a,
+            &b,
+            &create_test_working_context(false),
+            DiffKinds::all(),
+        );
+
+        // act
+        let patch = create_patch(&a, &b, &diffs).unwrap();
+
+        // assert
+        assert_eq!(
+            json!([
+                { "op": "remove", "path": "/3" },
+                { "op": "move", "from": "/2", "path": "/0" },
+                { "op": "add", "path": "/1", "value": 4 },
+                { "op": "move", "from": "/3", "path": "/2" },
+            ]),
+            serde_json::to_value(&patch).unwrap()
+        );
+        assert_eq!(b, apply_patch(&a, &patch).unwrap());
+    }
+
+    #[test]
+    fn test_apply_patch_errors() {
+        // arrange
+        let document = json!({ "a": { "b": [1] } });
+        let patches: Vec<Value> = vec![
+            json!([{ "op": "remove", "path": "/missing" }]),
+            json!([{ "op": "replace", "path": "/a/b/1", "value": 2 }]),
+            json!([{ "op": "add", "path": "/a/b/5", "value": 2 }]),
+            json!([{ "op": "add", "path": "/a/b/c/d", "value": 2 }]),
+            json!([{ "op": "move", "from": "/a", "path": "/a/c" }]),
+        ];
+
+        for patch in patches {
+            let patch: Vec<PatchOperation> = serde_json::from_value(patch).unwrap();
+
+            // act
+            let result = apply_patch(&document, &patch);
+
+            // assert
+            assert!(matches!(result, Err(Error::Patch { .. })), "{:?}", patch);
+        }
+    }
+
+    // Test utils
+
+    fn create_test_data() -> (Value, Value) {
+        let a = json!({
+            "a_has": "a_has",
+            "diff_string": "a",
+            "a_string_b_int": "1",
+            "a_object_b_string": { "key": "value" },
+            "diff_array": [1, 2, 3, 4],
+            "diff_length_array": [1, 2, 3],
+            "reordered_array": ["x", "y", "z"],
+            "array_of_objects": [
+                { "a_has": 1, "diff_number": 1 },
+                { "both_have": [1, 2] }
+            ],
+            "a/b~c": { "0": "zero" },
+            "nested": {
+                "a_has": "a_has",
+                "diff_boolean": true,
+                "diff_array": ["a", "b"]
+            }
+        });
+        let b = json!({
+            "b_has": "b_has",
+            "diff_string": "b",
+            "a_string_b_int": 1,
+            "a_object_b_string": "value",
+            "diff_array": [1, 2, 8, 4],
+            "diff_length_array": [1, 2],
+            "reordered_array": ["z", "x", "y"],
+            "array_of_objects": [
+                { "b_has": 1, "diff_number": 2 },
+                { "both_have": [2, 1] }
+            ],
+            "a/b~c": { "0": "one", "1": "two" },
+            "nested": {
+                "b_has": "b_has",
+                "diff_boolean": false,
+                "diff_array": ["b", "c"]
+            }
+        });
+
+        (a, b)
+    }
+
+    fn create_test_working_context(array_same_order: bool) -> WorkingContext {
+        WorkingContext::new(
+            WorkingFile::new("a.json".to_owned()),
+            WorkingFile::new("b.json".to_owned()),
+            Config::new(array_same_order),
+        )
+    }
+}
@@ -1,23 +1,42 @@
 pub mod core;
+pub mod cross;
+pub mod csv;
+pub mod document;
+pub mod ini;
 pub mod json;
+pub mod ndjson;
+pub mod properties;
+pub mod toml;
+pub mod xml;
 pub mod yaml;
 
 #[cfg(test)]
 mod tests {
+    use crate::core::error::Error;
     use crate::json::read_json_file;
     use crate::yaml::read_yaml_file;
 
     #[test]
     fn test_read_json_file() {
         let result = read_json_file("test_data.json");
-        println!("{:?}", result.unwrap());
-        assert!(true);
+        assert!(result.is_ok());
     }
 
     #[test]
     fn test_read_yaml_file() {
         let result = read_yaml_file("test_data.yaml");
-        println!("{:?}", result.unwrap());
-        assert!(true);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_read_missing_files() {
+        assert!(matches!(
+            read_json_file("missing.json"),
+            Err(Error::Io { .. })
+        ));
+        assert!(matches!(
+            read_yaml_file("missing.yaml"),
+            Err(Error::Io { .. })
+        ));
     }
 }
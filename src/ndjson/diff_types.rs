@@ -0,0 +1,11 @@
+use crate::core::path::Path;
+
+/// Describes how the records of 2 JSON Lines inputs are paired up for comparison
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecordPairing {
+    /// Records on the same line are compared. Blank lines hold no record.
+    ByLineNumber,
+    /// Records with the same value at the given path are compared, e.g. `id` or `event.id`.
+    /// Values of different types, like `1` and `"1"`, are different keys.
+    ByKey(Path),
+}
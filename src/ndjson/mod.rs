@@ -0,0 +1,547 @@
+//! Newline-delimited JSON (JSON Lines) inputs are compared record by record, without reading them in as a whole.
+//! Each line holds one JSON object. Only the records, that are waiting for their pair are held in memory.
+//!
+//! The paths of the differences start with the record, e.g. `records[line=3].status` when pairing by line number,
+//! or `records[id=42].status` and `records[id="42"].status` when pairing by key, as the key values are written as JSON.
+//! When pairing by key, a record lacking the key is identified by its line number instead, e.g. `records[3]`.
+//! A record, that has no pair in the other input is a `KeyDiff` with the path of the record, recording the record if the config asks for it.
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Lines, Read};
+
+use serde_json::{Map, Value};
+
+use crate::core::{
+    comparison::Comparison,
+    diff_types::{ComparisionResult, Config, DiffKinds, KeyDiff, WorkingContext, WorkingFile},
+    error::{Error, Result},
+    node::{identity_at, Node},
+    path::Path,
+    record_object,
+};
+
+use self::diff_types::RecordPairing;
+
+pub mod diff_types;
+
+/// A record read in from a single line of the input
+type Record = Map<String, Value>;
+
+/// Reads 2 JSON Lines files record by record and runs all the difference checks on the paired records.
+/// The file paths are used as the names of the files in the differences.
+///
+/// # Errors
+/// Returns an error if one of the files cannot be read, a line isn't valid JSON or a record isn't an object.
+pub fn compare_files(
+    file_path_a: &str,
+    file_path_b: &str,
+    config: Config,
+    pairing: &RecordPairing,
+) -> Result<ComparisionResult> {
+    let a = File::open(file_path_a).map_err(|error| Error::io(file_path_a, error))?;
+    let b = File::open(file_path_b).map_err(|error| Error::io(file_path_b, error))?;
+
+    let working_context = WorkingContext::new(
+        WorkingFile::from_path(file_path_a.to_owned()),
+        WorkingFile::from_path(file_path_b.to_owned()),
+        config,
+    );
+
+    compare_readers(a, b, &working_context, pairing, DiffKinds::all())
+}
+
+/// Reads 2 JSON Lines inputs record by record and runs the difference checks selected in `diff_kinds` on the paired records.
+/// The names of the working files are used in the errors.
+///
+/// # Errors
+/// Returns an error if one of the readers fails, a line isn't valid JSON or a record isn't an object.
+pub fn compare_readers<A: Read, B: Read>(
+    a: A,
+    b: B,
+    working_context: &WorkingContext,
+    pairing: &RecordPairing,
+    diff_kinds: DiffKinds,
+) -> Result<ComparisionResult> {
+    let mut a = RecordReader::new(a, &working_context.file_a.name);
+    let mut b = RecordReader::new(b, &working_context.file_b.name);
+    let mut comparison = RecordComparison::new(working_context, diff_kinds);
+
+    match pairing {
+        RecordPairing::ByLineNumber => loop {
+            match (a.read_line()?, b.read_line()?) {
+                (None, None) => break,
+                (a_line, b_line) => {
+                    let (a_number, a_record) = a_line.unwrap_or((0, None));
+                    let (b_number, b_record) = b_line.unwrap_or((0, None));
+                    let key = Path::root()
+                        .with_key("records")
+                        .with_selector(&format!("line={}", a_number.max(b_number)));
+
+                    match (a_record, b_record) {
+                        (Some(a_record), Some(b_record)) => {
+                            comparison.compare_records(&key, &a_record, &b_record)
+                        }
+                        (Some(a_record), None) => comparison.a_has(key, &a_record),
+                        (None, Some(b_record)) => comparison.b_has(key, &b_record),
+                        (None, None) => {}
+                    }
+                }
+            }
+        },
+        RecordPairing::ByKey(path) => {
+            let mut a_keys = RecordKeys::new(path);
+            let mut b_keys = RecordKeys::new(path);
+            let mut a_pending: BTreeMap<Path, Record> = BTreeMap::new();
+            let mut b_pending: BTreeMap<Path, Record> = BTreeMap::new();
+
+            loop {
+                let a_record = a.read_record()?;
+                let b_record = b.read_record()?;
+
+                if a_record.is_none() && b_record.is_none() {
+                    break;
+                }
+
+                if let Some((line, a_record)) = a_record {
+                    let key = a_keys.key(line, &a_record);
+                    match b_pending.remove(&key) {
+                        Some(b_record) => comparison.compare_records(&key, &a_record, &b_record),
+                        None => {
+                            a_pending.insert(key, a_record);
+                        }
+                    }
+                }
+
+                if let Some((line, b_record)) = b_record {
+                    let key = b_keys.key(line, &b_record);
+                    match a_pending.remove(&key) {
+                        Some(a_record) => comparison.compare_records(&key, &a_record, &b_record),
+                        None => {
+                            b_pending.insert(key, b_record);
+                        }
+                    }
+                }
+            }
+
+            a_pending
+                .into_iter()
+                .for_each(|(key, record)| comparison.a_has(key, &record));
+            b_pending
+                .into_iter()
+                .for_each(|(key, record)| comparison.b_has(key, &record));
+        }
+    }
+
+    Ok(comparison.into_result())
+}
+
+/// Reads the records of a JSON Lines input one by one
+struct RecordReader<'a, R: Read> {
+    lines: Lines<BufReader<R>>,
+    name: &'a str,
+    line: usize,
+}
+
+impl<'a, R: Read> RecordReader<'a, R> {
+    fn new(reader: R, name: &'a str) -> RecordReader<'a, R> {
+        RecordReader {
+            lines: BufReader::new(reader).lines(),
+            name,
+            line: 0,
+        }
+    }
+
+    /// Reads the next line with its number. The record is `None` if the line is blank.
+    fn read_line(&mut self) -> Result<Option<(usize, Option<Record>)>> {
+        let Some(line) = self.lines.next() else {
+            return Ok(None);
+        };
+        let line = line.map_err(|error| Error::io(self.name, error))?;
+        self.line += 1;
+
+        if line.trim().is_empty() {
+            return Ok(Some((self.line, None)));
+        }
+
+        let value: Value = serde_json::from_str(&line).map_err(|error| Error::Parse {
+            format: "NDJSON",
+            message: error.to_string(),
+            line: Some(self.line),
+            column: (error.column() > 0).then(|| error.column()),
+        })?;
+
+        match value {
+            Value::Object(record) => Ok(Some((self.line, Some(record)))),
+            other => Err(Error::Parse {
+                format: "NDJSON",
+                message: format!(
+                    "expected an object as the record, found {}",
+                    other.value_type()
+                ),
+                line: Some(self.line),
+                column: None,
+            }),
+        }
+    }
+
+    /// Reads the next record with its line number, skipping blank lines
+    fn read_record(&mut self) -> Result<Option<(usize, Record)>> {
+        while let Some((line, record)) = self.read_line()? {
+            if let Some(record) = record {
+                return Ok(Some((line, record)));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Creates the paths of the records of one input from their key fields.
+/// If several records share a key, the repeated keys get a `#2`, `#3`, ... suffix, so they are paired in their order.
+/// A record without a key field gets its line number as an index, which can't collide with a key.
+struct RecordKeys<'a> {
+    path: &'a Path,
+    occurrences: HashMap<String, usize>,
+}
+
+impl<'a> RecordKeys<'a> {
+    fn new(path: &'a Path) -> RecordKeys<'a> {
+        RecordKeys {
+            path,
+            occurrences: HashMap::new(),
+        }
+    }
+
+    fn key(&mut self, line: usize, record: &Record) -> Path {
+        let records_path = Path::root().with_key("records");
+        let Some(value) = identity_at::<Value>(record, self.path) else {
+            return records_path.with_index(line);
+        };
+        let identity = format!("{}={}", self.path, value);
+
+        let occurrence = self.occurrences.entry(identity.clone()).or_insert(0);
+        *occurrence += 1;
+
+        if *occurrence == 1 {
+            records_path.with_selector(&identity)
+        } else {
+            records_path.with_selector(&format!("{}#{}", identity, occurrence))
+        }
+    }
+}
+
+/// Collects the differences of the paired records
+struct RecordComparison<'a> {
+    comparison: Comparison<'a>,
+    missing_records: Vec<KeyDiff>,
+    working_context: &'a WorkingContext,
+    diff_kinds: DiffKinds,
+}
+
+impl<'a> RecordComparison<'a> {
+    fn new(working_context: &'a WorkingContext, diff_kinds: DiffKinds) -> RecordComparison<'a> {
+        RecordComparison {
+            comparison: Comparison::new(working_context, diff_kinds),
+            missing_records: vec![],
+            working_context,
+            diff_kinds,
+        }
+    }
+
+    fn compare_records(&mut self, key: &Path, a: &Record, b: &Record) {
+        if !self.working_context.config.skips(key) {
+            self.comparison.compare_objects::<Value, Value>(key, a, b);
+        }
+    }
+
+    /// Stores a `KeyDiff` for a record only `a` has
+    fn a_has(&mut self, key: Path, record: &Record) {
+        if self.diff_kinds.keys
+            && self
+                .working_context
+                .config
+                .reports_missing_object::<Value>(&key, record)
+        {
+            self.missing_records.push(
+                KeyDiff::new(
+                    key,
+                    self.working_context.file_a.name.clone(),
+                    self.working_context.file_b.name.clone(),
+                )
+                .with_json(record_object::<Value>(&self.working_context.config, record)),
+            );
+        }
+    }
+
+    /// Stores a `KeyDiff` for a record only `b` has
+    fn b_has(&mut self, key: Path, record: &Record) {
+        if self.diff_kinds.keys
+            && self
+                .working_context
+                .config
+                .reports_missing_object::<Value>(&key, record)
+        {
+            self.missing_records.push(
+                KeyDiff::new(
+                    key,
+                    self.working_context.file_b.name.clone(),
+                    self.working_context.file_a.name.clone(),
+                )
+                .with_json(record_object::<Value>(&self.working_context.config, record)),
+            );
+        }
+    }
+
+    fn into_result(self) -> ComparisionResult {
+        let (mut key_diffs, type_diffs, value_diffs, array_diffs) = self.comparison.into_result();
+        key_diffs.extend(self.missing_records);
+        (key_diffs, type_diffs, value_diffs, array_diffs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Read};
+
+    use serde_json::json;
+
+    use crate::core::{
+        diff_types::{
+            Config, DiffKinds, KeyDiff, TypeDiff, ValueDiff, WorkingContext, WorkingFile,
+        },
+        error::Error,
+    };
+
+    use super::{compare_readers, diff_types::RecordPairing};
+
+    const FILE_NAME_A: &str = "a.jsonl";
+    const FILE_NAME_B: &str = "b.jsonl";
+
+    #[test]
+    fn test_compare_readers_by_line_number() {
+        // arrange
+        let a = "{\"id\": 1, \"status\": \"open\"}\n{\"id\": 2}\n\n{\"id\": 4}\n";
+        let b = "{\"id\": 1, \"status\": 3}\n{\"id\": 2, \"extra\": true}\n{\"id\": 3}\n";
+
+        // act
+        let (key_diffs, type_diffs, value_diffs, array_diffs) = compare_readers(
+            a.as_bytes(),
+            b.as_bytes(),
+            &create_test_working_context(),
+            &RecordPairing::ByLineNumber,
+            DiffKinds::all(),
+        )
+        .unwrap();
+
+        // assert
+        assert_eq!(
+            vec![
+                KeyDiff::new(
+                    "records[line=2].extra".to_owned(),
+                    FILE_NAME_B.to_owned(),
+                    FILE_NAME_A.to_owned()
+                ),
+                KeyDiff::new(
+                    "records[line=3]".to_owned(),
+                    FILE_NAME_B.to_owned(),
+                    FILE_NAME_A.to_owned()
+                ),
+                KeyDiff::new(
+                    "records[line=4]".to_owned(),
+                    FILE_NAME_A.to_owned(),
+                    FILE_NAME_B.to_owned()
+                ),
+            ],
+            key_diffs
+        );
+        assert_eq!(
+            vec![TypeDiff::new(
+                "records[line=1].status".to_owned(),
+                "string".to_owned(),
+                "number".to_owned()
+            )],
+            type_diffs
+        );
+        assert_eq!(
+            vec![ValueDiff::new(
+                "records[line=1].status".to_owned(),
+                "open".to_owned(),
+                "3".to_owned()
+            )],
+            value_diffs
+        );
+        assert!(array_diffs.is_empty());
+    }
+
+    #[test]
+    fn test_compare_readers_by_key() {
+        // arrange
+        let a = "{\"event\": {\"id\": 1}, \"v\": 1}\n{\"event\": {\"id\": 2}, \"v\": 1}\n{\"event\": {\"id\": 3}, \"v\": 1}\n";
+        let b = "{\"event\": {\"id\": 3}, \"v\": 2}\n{\"event\": {\"id\": 1}, \"v\": 1}\n{\"event\": {\"id\": 4}, \"v\": 1}\n";
+
+        // act
+        let (key_diffs, _, value_diffs, _) = compare_readers(
+            a.as_bytes(),
+            b.as_bytes(),
+            &create_test_working_context(),
+            &RecordPairing::ByKey("event.id".into()),
+            DiffKinds::all(),
+        )
+        .unwrap();
+
+        // assert
+        assert_eq!(
+            vec![
+                KeyDiff::new(
+                    "records[event.id=2]".to_owned(),
+                    FILE_NAME_A.to_owned(),
+                    FILE_NAME_B.to_owned()
+                ),
+                KeyDiff::new(
+                    "records[event.id=4]".to_owned(),
+                    FILE_NAME_B.to_owned(),
+                    FILE_NAME_A.to_owned()
+                ),
+            ],
+            key_diffs
+        );
+        assert_eq!(
+            vec![ValueDiff::new(
+                "records[event.id=3].v".to_owned(),
+                "1".to_owned(),
+                "2".to_owned()
+            )],
+            value_diffs
+        );
+    }
+
+    #[test]
+    fn test_compare_readers_records_values() {
+        // arrange
+        let a = "{\"id\": 1, \"v\": 1}\n{\"id\": 2}\n";
+        let b = "{\"id\": 1, \"v\": 1}\n";
+        let mut working_context = create_test_working_context();
+        working_context.config.record_values = true;
+
+        // act
+        let (key_diffs, _, _, _) = compare_readers(
+            a.as_bytes(),
+            b.as_bytes(),
+            &working_context,
+            &RecordPairing::ByKey("id".into()),
+            DiffKinds::all(),
+        )
+        .unwrap();
+
+        // assert
+        assert_eq!(
+            vec![KeyDiff::new(
+                "records[id=2]".to_owned(),
+                FILE_NAME_A.to_owned(),
+                FILE_NAME_B.to_owned()
+            )
+            .with_json(Some(json!({ "id": 2 })))],
+            key_diffs
+        );
+    }
+
+    #[test]
+    fn test_compare_readers_by_typed_key() {
+        // arrange
+        let a = "{\"id\": 1}\n{\"v\": 1}\n";
+        let b = "{\"id\": \"1\"}\n{\"id\": null, \"v\": 1}\n";
+
+        // act
+        let (key_diffs, type_diffs, value_diffs, _) = compare_readers(
+            a.as_bytes(),
+            b.as_bytes(),
+            &create_test_working_context(),
+            &RecordPairing::ByKey("id".into()),
+            DiffKinds::all(),
+        )
+        .unwrap();
+
+        // assert
+        assert_eq!(
+            vec![
+                KeyDiff::new(
+                    "records[2]".to_owned(),
+                    FILE_NAME_A.to_owned(),
+                    FILE_NAME_B.to_owned()
+                ),
+                KeyDiff::new(
+                    "records[id=1]".to_owned(),
+                    FILE_NAME_A.to_owned(),
+                    FILE_NAME_B.to_owned()
+                ),
+                KeyDiff::new(
+                    "records[id=\"1\"]".to_owned(),
+                    FILE_NAME_B.to_owned(),
+                    FILE_NAME_A.to_owned()
+                ),
+                KeyDiff::new(
+                    "records[id=null]".to_owned(),
+                    FILE_NAME_B.to_owned(),
+                    FILE_NAME_A.to_owned()
+                ),
+            ],
+            key_diffs
+        );
+        assert!(type_diffs.is_empty());
+        assert!(value_diffs.is_empty());
+    }
+
+    #[test]
+    fn test_compare_readers_errors() {
+        struct FailingReader;
+
+        impl Read for FailingReader {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::new(io::ErrorKind::BrokenPipe, "broken pipe"))
+            }
+        }
+
+        let working_context = create_test_working_context();
+        let pairing = RecordPairing::ByLineNumber;
+
+        assert!(matches!(
+            compare_readers(
+                "{}\n{\"a\": \n".as_bytes(),
+                "{}\n".as_bytes(),
+                &working_context,
+                &pairing,
+                DiffKinds::all()
+            ),
+            Err(Error::Parse { line: Some(2), .. })
+        ));
+        assert!(matches!(
+            compare_readers(
+                "{}\n\n[1]\n".as_bytes(),
+                "{}\n".as_bytes(),
+                &working_context,
+                &pairing,
+                DiffKinds::all()
+            ),
+            Err(Error::Parse { line: Some(3), .. })
+        ));
+        assert!(matches!(
+            compare_readers(
+                "{}\n".as_bytes(),
+                FailingReader,
+                &working_context,
+                &pairing,
+                DiffKinds::all()
+            ),
+            Err(Error::Io { name, .. }) if name == FILE_NAME_B
+        ));
+    }
+
+    // Test utils
+
+    fn create_test_working_context() -> WorkingContext {
+        WorkingContext::new(
+            WorkingFile::new(FILE_NAME_A.to_owned()),
+            WorkingFile::new(FILE_NAME_B.to_owned()),
+            Config::new(false),
+        )
+    }
+}
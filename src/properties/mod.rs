@@ -0,0 +1,306 @@
+//! Java .properties files are read into the same nested model as JSON, so they can be checked against any other format:
+//! * Dotted keys are nested, so `a.b.c=1` is read in as `{ "a": { "b": { "c": 1 } } }`.
+//! * Values of `true` and `false` become booleans, values written as plain numbers become numbers, everything else stays a string.
+//! * Comments (`#`, `!`), the `=`, `:` and whitespace separators, line continuations and escapes are handled as in `java.util.Properties`.
+//! * A later entry of the same key overrides the earlier one.
+use std::fs;
+use std::io::Read;
+
+use serde_json::{Map, Number, Value};
+
+use crate::core::{
+    comparison,
+    diff_types::{ComparisionResult, DiffKinds, WorkingContext},
+    error::{Error, Result, DEPTH_LIMIT},
+};
+
+pub mod diff_types;
+
+/// Name used in the errors of data read from a reader
+const READER_NAME: &str = "properties input";
+
+/// Reads in a .properties file
+///
+/// # Errors
+/// Returns an error if the file cannot be read or a key conflicts with another one.
+pub fn read_properties_file(file_path: &str) -> Result<Map<String, Value>> {
+    let content = fs::read(file_path).map_err(|error| Error::io(file_path, error))?;
+    read_properties_slice(&content)
+}
+
+/// Reads in .properties data from a string
+///
+/// # Errors
+/// Returns an error if a key conflicts with another one, e.g. `a=1` and `a.b=2`, or has more than 128 segments.
+pub fn read_properties_str(content: &str) -> Result<Map<String, Value>> {
+    let mut document = Map::new();
+    let mut lines = content.lines().enumerate();
+
+    while let Some((index, line)) = lines.next() {
+        let mut entry = line.trim_start().to_owned();
+
+        if entry.is_empty() || entry.starts_with('#') || entry.starts_with('!') {
+            continue;
+        }
+
+        while ends_with_continuation(&entry) {
+            entry.pop();
+            match lines.next() {
+                Some((_, next)) => entry.push_str(next.trim_start()),
+                None => break,
+            }
+        }
+
+        let (key, value) = split_entry(&entry);
+        insert_dotted(
+            &mut document,
+            0,
+            &unescape(key),
+            parse_scalar(&unescape(value)),
+        )
+        .map_err(|error| error.into_error("properties", index + 1))?;
+    }
+
+    Ok(document)
+}
+
+/// Reads in .properties data from bytes
+///
+/// # Errors
+/// Returns an error if the content isn't valid UTF-8 or a key conflicts with another one.
+pub fn read_properties_slice(content: &[u8]) -> Result<Map<String, Value>> {
+    let content = std::str::from_utf8(content).map_err(|error| Error::Parse {
+        format: "properties",
+        message: error.to_string(),
+        line: None,
+        column: None,
+    })?;
+    read_properties_str(content)
+}
+
+/// Reads in .properties data from any reader, like stdin or a network stream
+///
+/// # Errors
+/// Returns an error if the reader fails or a key conflicts with another one.
+pub fn read_properties_reader<R: Read>(mut reader: R) -> Result<Map<String, Value>> {
+    let mut content = vec![];
+    reader
+        .read_to_end(&mut content)
+        .map_err(|error| Error::io(READER_NAME, error))?;
+    read_properties_slice(&content)
+}
+
+/// Runs the difference checks selected in `diff_kinds` on `a` against `b` in a single traversal
+pub fn compare(
+    a: &Map<String, Value>,
+    b: &Map<String, Value>,
+    working_context: &WorkingContext,
+    diff_kinds: DiffKinds,
+) -> ComparisionResult {
+    comparison::compare::<Value, Value>(a, b, working_context, diff_kinds)
+}
+
+/// Tells why a line of an untyped format couldn't be read
+pub(crate) enum LineError {
+    Invalid(String),
+    /// The line nests its value deeper than `DEPTH_LIMIT`
+    TooDeep,
+}
+
+impl LineError {
+    /// Turns the error into the error of the line with the 1 based number
+    pub(crate) fn into_error(self, format: &'static str, line: usize) -> Error {
+        match self {
+            LineError::Invalid(message) => Error::Parse {
+                format,
+                message,
+                line: Some(line),
+                column: None,
+            },
+            LineError::TooDeep => Error::depth_limit(format),
+        }
+    }
+}
+
+impl From<String> for LineError {
+    fn from(message: String) -> LineError {
+        LineError::Invalid(message)
+    }
+}
+
+/// Inserts the value under the dotted key, creating the objects on the way. `depth` is the depth of the object in the read-in data.
+/// Returns an error if the key or one of its parents already holds a value of another kind, or the value would be nested deeper than `DEPTH_LIMIT`.
+pub(crate) fn insert_dotted(
+    object: &mut Map<String, Value>,
+    depth: usize,
+    key: &str,
+    value: Value,
+) -> std::result::Result<(), LineError> {
+    let conflict = || LineError::Invalid(format!("key `{}` conflicts with a key set before", key));
+
+    let segments: Vec<&str> = key.split('.').collect();
+    if depth + segments.len() > DEPTH_LIMIT {
+        return Err(LineError::TooDeep);
+    }
+
+    let Some((last, parents)) = segments.split_last() else {
+        return Err(conflict());
+    };
+
+    let mut current = object;
+    for segment in parents {
+        current = match current
+            .entry(*segment)
+            .or_insert_with(|| Value::Object(Map::new()))
+        {
+            Value::Object(child) => child,
+            _ => return Err(conflict()),
+        };
+    }
+
+    if matches!(current.get(*last), Some(Value::Object(_))) {
+        return Err(conflict());
+    }
+
+    current.insert((*last).to_owned(), value);
+    Ok(())
+}
+
+/// Turns the text of an untyped format into a boolean or a number, if it is written as one, or a string otherwise.
+/// Numbers are only recognized if they are written the way they would be displayed, so e.g. `007` and `1.50` stay strings.
+pub(crate) fn parse_scalar(text: &str) -> Value {
+    match text {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        _ => match text.parse::<Number>() {
+            Ok(number) if number.to_string() == text => Value::Number(number),
+            _ => Value::String(text.to_owned()),
+        },
+    }
+}
+
+fn ends_with_continuation(line: &str) -> bool {
+    line.chars().rev().take_while(|c| *c == '\\').count() % 2 == 1
+}
+
+fn split_entry(entry: &str) -> (&str, &str) {
+    let mut escaped = false;
+    let mut key_end = entry.len();
+
+    for (index, c) in entry.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == '=' || c == ':' || c.is_whitespace() {
+            key_end = index;
+            break;
+        }
+    }
+
+    let rest = entry[key_end..].trim_start();
+    let rest = rest
+        .strip_prefix(['=', ':'])
+        .map_or(rest, |rest| rest.trim_start());
+
+    (&entry[..key_end], rest)
+}
+
+fn unescape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('t') => result.push('\t'),
+            Some('n') => result.push('\n'),
+            Some('r') => result.push('\r'),
+            Some('f') => result.push('\u{c}'),
+            Some('u') => {
+                let code: String = chars.by_ref().take(4).collect();
+                match u32::from_str_radix(&code, 16).ok().and_then(char::from_u32) {
+                    Some(decoded) => result.push(decoded),
+                    None => {
+                        result.push_str("\\u");
+                        result.push_str(&code);
+                    }
+                }
+            }
+            Some(other) => result.push(other),
+            None => {}
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::core::error::Error;
+
+    use super::read_properties_str;
+
+    #[test]
+    fn test_read_properties_str() {
+        // act
+        let result = read_properties_str(
+            "# comment\n\
+             ! another comment\n\
+             server.port=8080\n\
+             server.address : localhost\n\
+             spring.application.name my\\ app\n\
+             spring.main.lazy-initialization=true\n\
+             version=1.50\n\
+             message=first \\\n    second\n\
+             escaped\\=key=\\u00e9\\t\n\
+             server.port=9090\n",
+        )
+        .unwrap();
+
+        // assert
+        assert_eq!(
+            json!({
+                "server": { "port": 9090, "address": "localhost" },
+                "spring": {
+                    "application": { "name": "my app" },
+                    "main": { "lazy-initialization": true }
+                },
+                "version": "1.50",
+                "message": "first second",
+                "escaped=key": "é\t"
+            }),
+            serde_json::Value::Object(result)
+        );
+    }
+
+    #[test]
+    fn test_read_properties_str_conflicting_keys() {
+        assert!(matches!(
+            read_properties_str("a=1\na.b=2\n"),
+            Err(Error::Parse { line: Some(2), .. })
+        ));
+        assert!(matches!(
+            read_properties_str("a.b=2\na=1\n"),
+            Err(Error::Parse { line: Some(2), .. })
+        ));
+    }
+
+    #[test]
+    fn test_read_properties_str_limit_exceeded() {
+        assert!(matches!(
+            read_properties_str(&format!("{}=1\n", vec!["k"; 300000].join("."))),
+            Err(Error::LimitExceeded {
+                format: "properties",
+                ..
+            })
+        ));
+        assert!(read_properties_str(&format!("{}=1\n", vec!["k"; 128].join("."))).is_ok());
+    }
+}
@@ -0,0 +1,57 @@
+use std::borrow::Cow;
+
+use ::toml::{Table, Value};
+
+use crate::core::{
+    diff_types::ValueType,
+    node::{Node, NodeObject},
+};
+
+/// Holds the data required to run a difference check on TOML data
+pub type CheckingData<'a, T> = crate::core::diff_types::CheckingData<'a, T, Value>;
+
+impl Node for Value {
+    type Object = Table;
+
+    fn value_type(&self) -> ValueType {
+        match self {
+            Value::Boolean(_) => ValueType::Boolean,
+            Value::Integer(_) | Value::Float(_) => ValueType::Number,
+            Value::String(_) => ValueType::String,
+            Value::Datetime(_) => ValueType::DateTime,
+            Value::Array(_) => ValueType::Array,
+            Value::Table(_) => ValueType::Object,
+        }
+    }
+
+    fn as_object(&self) -> Option<&Table> {
+        self.as_table()
+    }
+
+    fn as_array(&self) -> Option<&[Value]> {
+        Value::as_array(self).map(Vec::as_slice)
+    }
+
+    fn to_display_string(&self) -> String {
+        match self {
+            // String values are quoted by default if to_string() is called on them, so we return them as they are.
+            Value::String(value) => value.to_owned(),
+            // Datetimes on their own are serialized in an internal representation, so we format them directly.
+            Value::Datetime(value) => value.to_string(),
+            value => value.to_string(),
+        }
+    }
+}
+
+impl NodeObject<Value> for Table {
+    fn entries(&self) -> Box<dyn Iterator<Item = (Cow<'_, str>, &Value)> + '_> {
+        Box::new(
+            self.iter()
+                .map(|(key, value)| (Cow::from(key.as_str()), value)),
+        )
+    }
+
+    fn get(&self, key: &str) -> Option<&Value> {
+        Table::get(self, key)
+    }
+}
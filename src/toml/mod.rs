@@ -0,0 +1,235 @@
+use std::fs;
+use std::io::Read;
+
+use ::toml::{Table, Value};
+
+use crate::core::{
+    comparison,
+    diff_types::{ComparisionResult, DiffKinds, WorkingContext},
+    error::{Error, Result},
+};
+
+pub mod diff_types;
+
+/// Name used in the errors of data read from a reader
+const READER_NAME: &str = "TOML input";
+
+/// Reads in a toml file
+///
+/// # Errors
+/// Returns an error if the file cannot be read or it isn't valid TOML.
+pub fn read_toml_file(file_path: &str) -> Result<Table> {
+    let content = fs::read(file_path).map_err(|error| Error::io(file_path, error))?;
+    read_toml_slice(&content)
+}
+
+/// Reads in toml data from a string
+///
+/// # Errors
+/// Returns an error if the content isn't valid TOML.
+pub fn read_toml_str(content: &str) -> Result<Table> {
+    ::toml::from_str(content).map_err(|error| Error::from_toml(error, content))
+}
+
+/// Reads in toml data from bytes
+///
+/// # Errors
+/// Returns an error if the content isn't valid UTF-8 or valid TOML.
+pub fn read_toml_slice(content: &[u8]) -> Result<Table> {
+    let content = std::str::from_utf8(content).map_err(|error| Error::Parse {
+        format: "TOML",
+        message: error.to_string(),
+        line: None,
+        column: None,
+    })?;
+    read_toml_str(content)
+}
+
+/// Reads in toml data from any reader, like stdin or a network stream
+///
+/// # Errors
+/// Returns an error if the reader fails or the content isn't valid TOML.
+pub fn read_toml_reader<R: Read>(mut reader: R) -> Result<Table> {
+    let mut content = vec![];
+    reader
+        .read_to_end(&mut content)
+        .map_err(|error| Error::io(READER_NAME, error))?;
+    read_toml_slice(&content)
+}
+
+/// Runs the difference checks selected in `diff_kinds` on `a` against `b` in a single traversal
+pub fn compare(
+    a: &Table,
+    b: &Table,
+    working_context: &WorkingContext,
+    diff_kinds: DiffKinds,
+) -> ComparisionResult {
+    comparison::compare::<Value, Value>(a, b, working_context, diff_kinds)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::{
+        diff_types::{
+            Checker, Config, DiffKinds, TypeDiff, ValueDiff, ValueType, WorkingContext, WorkingFile,
+        },
+        error::Error,
+        node::Node,
+    };
+
+    use super::{compare, diff_types::CheckingData, read_toml_reader, read_toml_str};
+
+    const FILE_NAME_A: &str = "a.toml";
+    const FILE_NAME_B: &str = "b.toml";
+
+    #[test]
+    fn test_read_toml_str() {
+        let result = read_toml_str(
+            r#"
+            title = "TOML"
+
+            [owner]
+            dob = 1979-05-27T07:32:00-08:00
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(ValueType::String, result["title"].value_type());
+        assert_eq!(ValueType::DateTime, result["owner"]["dob"].value_type());
+    }
+
+    #[test]
+    fn test_read_toml_reader() {
+        let result = read_toml_reader("a = [1, 2]".as_bytes()).unwrap();
+
+        assert_eq!(ValueType::Array, result["a"].value_type());
+    }
+
+    #[test]
+    fn test_read_toml_str_reports_position() {
+        assert!(matches!(
+            read_toml_str("a = 1\nb = = 2\n"),
+            Err(Error::Parse {
+                line: Some(2),
+                column: Some(5),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_read_toml_str_limit_exceeded() {
+        assert!(matches!(
+            read_toml_str(&format!("a = {}{}", "[".repeat(200), "]".repeat(200))),
+            Err(Error::LimitExceeded { format: "TOML", .. })
+        ));
+        assert!(matches!(
+            read_toml_str(&format!("a = {}1{}", "{b = ".repeat(200), "}".repeat(200))),
+            Err(Error::LimitExceeded { format: "TOML", .. })
+        ));
+    }
+
+    #[test]
+    fn test_find_type_diffs() {
+        // arrange
+        let a = read_toml_str(
+            r#"
+            both_string = "a"
+            a_datetime_b_string = 1979-05-27
+            [nested]
+            a_int_b_float = 1
+            a_float_b_string = 1.5
+            "#,
+        )
+        .unwrap();
+        let b = read_toml_str(
+            r#"
+            both_string = "b"
+            a_datetime_b_string = "1979-05-27"
+            [nested]
+            a_int_b_float = 1.0
+            a_float_b_string = "1.5"
+            "#,
+        )
+        .unwrap();
+
+        let expected = vec![
+            TypeDiff::new(
+                "a_datetime_b_string".to_owned(),
+                "datetime".to_owned(),
+                "string".to_owned(),
+            ),
+            TypeDiff::new(
+                "nested.a_float_b_string".to_owned(),
+                "number".to_owned(),
+                "string".to_owned(),
+            ),
+        ];
+
+        let working_context = create_test_working_context(false);
+        let mut type_checker: CheckingData<TypeDiff> =
+            CheckingData::new("", &a, &b, &working_context);
+
+        // act
+        type_checker.check();
+
+        // assert
+        assert_array(&expected, type_checker.diffs());
+    }
+
+    #[test]
+    fn test_compare() {
+        // arrange
+        let a = read_toml_str(
+            r#"
+            same = 1979-05-27T07:32:00Z
+            diff_datetime = 1979-05-27T07:32:00Z
+            diff_string = "a"
+            "#,
+        )
+        .unwrap();
+        let b = read_toml_str(
+            r#"
+            same = 1979-05-27T07:32:00Z
+            diff_datetime = 1980-05-27T07:32:00Z
+            diff_string = "b"
+            "#,
+        )
+        .unwrap();
+
+        let expected = vec![
+            ValueDiff::new(
+                "diff_datetime".to_owned(),
+                "1979-05-27T07:32:00Z".to_owned(),
+                "1980-05-27T07:32:00Z".to_owned(),
+            ),
+            ValueDiff::new("diff_string".to_owned(), "a".to_owned(), "b".to_owned()),
+        ];
+
+        let working_context = create_test_working_context(false);
+
+        // act
+        let (key_diffs, type_diffs, value_diffs, array_diffs) =
+            compare(&a, &b, &working_context, DiffKinds::all());
+
+        // assert
+        assert!(key_diffs.is_empty());
+        assert!(type_diffs.is_empty());
+        assert_array(&expected, &value_diffs);
+        assert!(array_diffs.is_empty());
+    }
+
+    // Test utils
+
+    fn create_test_working_context(array_same_order: bool) -> WorkingContext {
+        let config = Config::new(array_same_order);
+        let working_file_a = WorkingFile::new(FILE_NAME_A.to_owned());
+        let working_file_b = WorkingFile::new(FILE_NAME_B.to_owned());
+        WorkingContext::new(working_file_a, working_file_b, config)
+    }
+
+    fn assert_array<T: PartialEq>(expected: &[T], result: &[T]) {
+        assert_eq!(expected.len(), result.len());
+        assert!(expected.iter().all(|item| result.contains(item)));
+    }
+}
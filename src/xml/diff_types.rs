@@ -0,0 +1,3 @@
+/// Holds the data required to run a difference check on XML data.
+/// XML data is read into the same model as JSON, so it uses the JSON checkers.
+pub type CheckingData<'a, T> = crate::json::diff_types::CheckingData<'a, T>;
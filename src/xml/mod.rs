@@ -0,0 +1,516 @@
+//! XML documents are read into the same model as JSON, so they can be checked against any other format:
+//! * An element becomes a field keyed by its name. The outermost element is the only field of the read-in object.
+//! * Attributes become fields keyed by their names prefixed with `@`, e.g. `project.@version`. Element names can't start with `@`, so attributes can't be mistaken for child elements.
+//! * An element, that only contains text becomes a string. If it has attributes or child elements as well, the text is stored in the `#text` field.
+//! * An element without any content becomes an empty string.
+//! * Repeated sibling elements with the same name become an array.
+//!   When compared, a single element is turned into a one-item array, if the other data set repeats it, so adding a sibling shows up as an array difference.
+use std::fs;
+use std::io::Read;
+
+use quick_xml::{
+    events::{BytesStart, Event},
+    Reader,
+};
+use serde_json::{Map, Value};
+
+use crate::core::{
+    comparison,
+    diff_types::{ComparisionResult, DiffKinds, WorkingContext},
+    error::{position, Error, Result, DEPTH_LIMIT},
+};
+
+pub mod diff_types;
+
+/// Name used in the errors of data read from a reader
+const READER_NAME: &str = "XML input";
+
+/// Prefix of the keys of attributes
+pub const ATTRIBUTE_PREFIX: &str = "@";
+
+/// Key of the text content of elements, that have attributes or child elements as well
+pub const TEXT_KEY: &str = "#text";
+
+/// Reads in an xml file
+///
+/// # Errors
+/// Returns an error if the file cannot be read or it isn't valid XML.
+pub fn read_xml_file(file_path: &str) -> Result<Map<String, Value>> {
+    let content = fs::read(file_path).map_err(|error| Error::io(file_path, error))?;
+    read_xml_slice(&content)
+}
+
+/// Reads in xml data from a string
+///
+/// # Errors
+/// Returns an error if the content isn't valid XML or its elements are nested deeper than 128 levels.
+pub fn read_xml_str(content: &str) -> Result<Map<String, Value>> {
+    let mut reader = Reader::from_str(content);
+    reader.config_mut().trim_text(true);
+
+    parse(&mut reader).map_err(|failure| match failure {
+        ParseFailure::TooDeep => Error::depth_limit("XML"),
+        ParseFailure::Invalid(message) => {
+            let (line, column) = position(content, reader.error_position() as usize);
+            Error::Parse {
+                format: "XML",
+                message,
+                line: Some(line),
+                column: Some(column),
+            }
+        }
+    })
+}
+
+/// Reads in xml data from bytes
+///
+/// # Errors
+/// Returns an error if the content isn't valid UTF-8 or valid XML.
+pub fn read_xml_slice(content: &[u8]) -> Result<Map<String, Value>> {
+    let content = std::str::from_utf8(content).map_err(|error| Error::Parse {
+        format: "XML",
+        message: error.to_string(),
+        line: None,
+        column: None,
+    })?;
+    read_xml_str(content)
+}
+
+/// Reads in xml data from any reader, like stdin or a network stream
+///
+/// # Errors
+/// Returns an error if the reader fails or the content isn't valid XML.
+pub fn read_xml_reader<R: Read>(mut reader: R) -> Result<Map<String, Value>> {
+    let mut content = vec![];
+    reader
+        .read_to_end(&mut content)
+        .map_err(|error| Error::io(READER_NAME, error))?;
+    read_xml_slice(&content)
+}
+
+/// Runs the difference checks selected in `diff_kinds` on `a` against `b` in a single traversal.
+/// The repeated elements are aligned first with `align_repeated_elements`.
+pub fn compare(
+    a: &Map<String, Value>,
+    b: &Map<String, Value>,
+    working_context: &WorkingContext,
+    diff_kinds: DiffKinds,
+) -> ComparisionResult {
+    let mut a = a.clone();
+    let mut b = b.clone();
+    align_repeated_elements(&mut a, &mut b);
+    comparison::compare::<Value, Value>(&a, &b, working_context, diff_kinds)
+}
+
+/// Turns the single elements into one-item arrays, where the other data set repeats the same element.
+/// Use it before running the checkers on xml data, so a sibling added to a single element is an array difference instead of a type difference.
+pub fn align_repeated_elements(a: &mut Map<String, Value>, b: &mut Map<String, Value>) {
+    for (key, a_value) in a.iter_mut() {
+        if let Some(b_value) = b.get_mut(key) {
+            align_repeated_values(a_value, b_value);
+        }
+    }
+}
+
+/// Aligns the repeated elements of 2 element values. The items of repeated elements are aligned by their position.
+pub(crate) fn align_repeated_values(a: &mut Value, b: &mut Value) {
+    match (a, b) {
+        (Value::Object(a), Value::Object(b)) => align_repeated_elements(a, b),
+        (Value::Array(a), Value::Array(b)) => {
+            for (a_item, b_item) in a.iter_mut().zip(b.iter_mut()) {
+                align_repeated_values(a_item, b_item);
+            }
+        }
+        // Element values are never arrays, so an array always holds repeated siblings
+        (Value::Array(items), single) | (single, Value::Array(items)) => {
+            let mut item = single.take();
+            if let Some(first) = items.first_mut() {
+                align_repeated_values(first, &mut item);
+            }
+            *single = Value::Array(vec![item]);
+        }
+        _ => {}
+    }
+}
+
+/// An element, that is being read in
+struct ElementFrame {
+    name: String,
+    fields: Map<String, Value>,
+    texts: Vec<String>,
+}
+
+impl ElementFrame {
+    fn new(start: &BytesStart) -> std::result::Result<ElementFrame, String> {
+        let mut fields = Map::new();
+
+        for attribute in start.attributes() {
+            let attribute = attribute.map_err(|error| error.to_string())?;
+            let key = String::from_utf8_lossy(attribute.key.as_ref());
+            let value = attribute
+                .unescape_value()
+                .map_err(|error| error.to_string())?;
+            fields.insert(
+                format!("{}{}", ATTRIBUTE_PREFIX, key),
+                Value::String(value.into_owned()),
+            );
+        }
+
+        Ok(ElementFrame {
+            name: String::from_utf8_lossy(start.name().as_ref()).into_owned(),
+            fields,
+            texts: vec![],
+        })
+    }
+
+    fn add_child(&mut self, name: String, value: Value) {
+        match self.fields.get_mut(&name) {
+            // Element values are never arrays, so an array always holds repeated siblings
+            Some(Value::Array(items)) => items.push(value),
+            Some(existing) => {
+                let first = existing.take();
+                *existing = Value::Array(vec![first, value]);
+            }
+            None => {
+                self.fields.insert(name, value);
+            }
+        }
+    }
+
+    fn into_value(mut self) -> (String, Value) {
+        let text = self.texts.join(" ");
+
+        let value = if self.fields.is_empty() {
+            Value::String(text)
+        } else {
+            if !text.is_empty() {
+                self.fields.insert(TEXT_KEY.to_owned(), Value::String(text));
+            }
+            Value::Object(self.fields)
+        };
+
+        (self.name, value)
+    }
+}
+
+/// Tells why the elements couldn't be read
+enum ParseFailure {
+    Invalid(String),
+    /// The elements are nested deeper than `DEPTH_LIMIT`
+    TooDeep,
+}
+
+impl From<String> for ParseFailure {
+    fn from(message: String) -> ParseFailure {
+        ParseFailure::Invalid(message)
+    }
+}
+
+fn parse(reader: &mut Reader<&[u8]>) -> std::result::Result<Map<String, Value>, ParseFailure> {
+    let mut stack: Vec<ElementFrame> = vec![];
+    let mut root: Option<(String, Value)> = None;
+
+    loop {
+        let element = match reader.read_event().map_err(|error| error.to_string())? {
+            Event::Start(start) => {
+                if stack.len() >= DEPTH_LIMIT {
+                    return Err(ParseFailure::TooDeep);
+                }
+                stack.push(ElementFrame::new(&start)?);
+                None
+            }
+            Event::Empty(start) => Some(ElementFrame::new(&start)?.into_value()),
+            Event::End(_) => stack.pop().map(ElementFrame::into_value),
+            Event::Text(text) => {
+                let text = text.unescape().map_err(|error| error.to_string())?;
+                push_text(&mut stack, &text)?;
+                None
+            }
+            Event::CData(data) => {
+                let text = data.decode().map_err(|error| error.to_string())?;
+                push_text(&mut stack, &text)?;
+                None
+            }
+            Event::Eof => break,
+            _ => None,
+        };
+
+        if let Some((name, value)) = element {
+            match stack.last_mut() {
+                Some(parent) => parent.add_child(name, value),
+                None if root.is_none() => root = Some((name, value)),
+                None => return Err("multiple root elements".to_owned().into()),
+            }
+        }
+    }
+
+    if !stack.is_empty() {
+        return Err("unexpected end of document".to_owned().into());
+    }
+
+    let (name, value) = root.ok_or_else(|| "missing root element".to_owned())?;
+    let mut document = Map::new();
+    document.insert(name, value);
+    Ok(document)
+}
+
+fn push_text(stack: &mut [ElementFrame], text: &str) -> std::result::Result<(), String> {
+    let text = text.trim();
+
+    if text.is_empty() {
+        return Ok(());
+    }
+
+    match stack.last_mut() {
+        Some(frame) => {
+            frame.texts.push(text.to_owned());
+            Ok(())
+        }
+        None => Err("text outside of the root element".to_owned()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::core::{
+        diff_types::{
+            ArrayDiff, ArrayDiffDesc, Config, DiffKinds, KeyDiff, ValueDiff, WorkingContext,
+            WorkingFile,
+        },
+        error::Error,
+    };
+
+    use super::{compare, read_xml_str};
+
+    const FILE_NAME_A: &str = "a.xml";
+    const FILE_NAME_B: &str = "b.xml";
+
+    #[test]
+    fn test_read_xml_str() {
+        // act
+        let result = read_xml_str(
+            r#"<?xml version="1.0"?>
+            <!-- comment -->
+            <project version="4.0.0">
+                <name>libdtf &amp; co</name>
+                <empty/>
+                <modules>
+                    <module>core</module>
+                    <module>json</module>
+                </modules>
+                <description lang="en">Text <![CDATA[<raw>]]></description>
+            </project>"#,
+        )
+        .unwrap();
+
+        // assert
+        assert_eq!(
+            json!({
+                "project": {
+                    "@version": "4.0.0",
+                    "name": "libdtf & co",
+                    "empty": "",
+                    "modules": { "module": ["core", "json"] },
+                    "description": { "@lang": "en", "#text": "Text <raw>" }
+                }
+            }),
+            serde_json::Value::Object(result)
+        );
+    }
+
+    #[test]
+    fn test_read_xml_str_reports_position() {
+        assert!(matches!(
+            read_xml_str("<a>\n  <b></c>\n</a>"),
+            Err(Error::Parse { line: Some(2), .. })
+        ));
+        assert!(matches!(
+            read_xml_str("<a></a><b></b>"),
+            Err(Error::Parse { .. })
+        ));
+        assert!(matches!(read_xml_str(""), Err(Error::Parse { .. })));
+    }
+
+    #[test]
+    fn test_read_xml_str_limit_exceeded() {
+        assert!(matches!(
+            read_xml_str(&format!(
+                "{}{}",
+                "<a>".repeat(200000),
+                "</a>".repeat(200000)
+            )),
+            Err(Error::LimitExceeded { format: "XML", .. })
+        ));
+        assert!(read_xml_str(&format!("{}{}", "<a>".repeat(128), "</a>".repeat(128))).is_ok());
+    }
+
+    #[test]
+    fn test_compare() {
+        // arrange
+        let a = read_xml_str(
+            r#"<config version="1">
+                <a_has>1</a_has>
+                <name>a</name>
+                <single_a_repeated_b>x</single_a_repeated_b>
+                <items><item>1</item><item>2</item></items>
+            </config>"#,
+        )
+        .unwrap();
+        let b = read_xml_str(
+            r#"<config version="2" b_has="1">
+                <name>b</name>
+                <single_a_repeated_b>x</single_a_repeated_b>
+                <single_a_repeated_b>y</single_a_repeated_b>
+                <items><item>1</item><item>3</item></items>
+            </config>"#,
+        )
+        .unwrap();
+
+        let working_context = WorkingContext::new(
+            WorkingFile::new(FILE_NAME_A.to_owned()),
+            WorkingFile::new(FILE_NAME_B.to_owned()),
+            Config::new(false),
+        );
+
+        // act
+        let (key_diffs, type_diffs, value_diffs, array_diffs) =
+            compare(&a, &b, &working_context, DiffKinds::all());
+
+        // assert
+        assert_array(
+            &[
+                KeyDiff::new(
+                    "config.a_has".to_owned(),
+                    FILE_NAME_A.to_owned(),
+                    FILE_NAME_B.to_owned(),
+                ),
+                KeyDiff::new(
+                    "config.@b_has".to_owned(),
+                    FILE_NAME_B.to_owned(),
+                    FILE_NAME_A.to_owned(),
+                ),
+            ],
+            &key_diffs,
+        );
+        assert!(type_diffs.is_empty());
+        assert_array(
+            &[
+                ValueDiff::new("config.@version".to_owned(), "1".to_owned(), "2".to_owned()),
+                ValueDiff::new("config.name".to_owned(), "a".to_owned(), "b".to_owned()),
+                ValueDiff::new(
+                    "config.single_a_repeated_b".to_owned(),
+                    "Array differences present".to_owned(),
+                    "Array differences present".to_owned(),
+                ),
+                ValueDiff::new(
+                    "config.items.item".to_owned(),
+                    "Array differences present".to_owned(),
+                    "Array differences present".to_owned(),
+                ),
+            ],
+            &value_diffs,
+        );
+        assert_array(
+            &[
+                ArrayDiff::new(
+                    "config.single_a_repeated_b".to_owned(),
+                    ArrayDiffDesc::BHas,
+                    "y".to_owned(),
+                ),
+                ArrayDiff::new(
+                    "config.single_a_repeated_b".to_owned(),
+                    ArrayDiffDesc::AMisses,
+                    "y".to_owned(),
+                ),
+                ArrayDiff::new(
+                    "config.items.item".to_owned(),
+                    ArrayDiffDesc::AHas,
+                    "2".to_owned(),
+                ),
+                ArrayDiff::new(
+                    "config.items.item".to_owned(),
+                    ArrayDiffDesc::BMisses,
+                    "2".to_owned(),
+                ),
+                ArrayDiff::new(
+                    "config.items.item".to_owned(),
+                    ArrayDiffDesc::BHas,
+                    "3".to_owned(),
+                ),
+                ArrayDiff::new(
+                    "config.items.item".to_owned(),
+                    ArrayDiffDesc::AMisses,
+                    "3".to_owned(),
+                ),
+            ],
+            &array_diffs,
+        );
+    }
+
+    #[test]
+    fn test_compare_single_to_repeated() {
+        // arrange
+        let a = read_xml_str(
+            r#"<project><dependencies>
+                <dependency><artifactId>core</artifactId></dependency>
+            </dependencies><exclusions><exclusion>log</exclusion></exclusions></project>"#,
+        )
+        .unwrap();
+        let b = read_xml_str(
+            r#"<project><dependencies>
+                <dependency><artifactId>core</artifactId></dependency>
+                <dependency><artifactId>json</artifactId></dependency>
+            </dependencies><exclusions><exclusion>log</exclusion><exclusion>xml</exclusion></exclusions></project>"#,
+        )
+        .unwrap();
+
+        let working_context = WorkingContext::new(
+            WorkingFile::new(FILE_NAME_A.to_owned()),
+            WorkingFile::new(FILE_NAME_B.to_owned()),
+            Config::new(false),
+        );
+
+        // act
+        let (key_diffs, type_diffs, value_diffs, array_diffs) =
+            compare(&a, &b, &working_context, DiffKinds::all());
+
+        // assert
+        assert!(key_diffs.is_empty());
+        assert!(type_diffs.is_empty());
+        assert_eq!(2, value_diffs.len());
+        assert_array(
+            &[
+                ArrayDiff::new(
+                    "project.dependencies.dependency".to_owned(),
+                    ArrayDiffDesc::BHas,
+                    r#"{"artifactId":"json"}"#.to_owned(),
+                ),
+                ArrayDiff::new(
+                    "project.dependencies.dependency".to_owned(),
+                    ArrayDiffDesc::AMisses,
+                    r#"{"artifactId":"json"}"#.to_owned(),
+                ),
+                ArrayDiff::new(
+                    "project.exclusions.exclusion".to_owned(),
+                    ArrayDiffDesc::BHas,
+                    "xml".to_owned(),
+                ),
+                ArrayDiff::new(
+                    "project.exclusions.exclusion".to_owned(),
+                    ArrayDiffDesc::AMisses,
+                    "xml".to_owned(),
+                ),
+            ],
+            &array_diffs,
+        );
+    }
+
+    // Test utils
+
+    fn assert_array<T: PartialEq>(expected: &[T], result: &[T]) {
+        assert_eq!(expected.len(), result.len());
+        assert!(expected.iter().all(|item| result.contains(item)));
+    }
+}
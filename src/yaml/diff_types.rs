@@ -1,6 +1,13 @@
-use serde_yaml::Mapping;
+use std::borrow::Cow;
 
-use crate::core::diff_types::{Diff, DiffCollection, Stringable, WorkingContext};
+use serde_yaml::{Mapping, Value};
+
+use crate::core::{
+    diff_types::{Stringable, ValueType},
+    merge::MergeNode,
+    node::{Node, NodeObject},
+    path::{Path, PathSegment},
+};
 
 impl Stringable for serde_yaml::Value {
     fn to_string(&self) -> String {
@@ -14,34 +21,187 @@ impl Stringable for serde_yaml::Value {
     }
 }
 
-/// Holds the data required to run a difference check
-pub struct CheckingData<'a, T: Diff> {
-    /// Holds the collected differences
-    pub diffs: DiffCollection<T>,
-    /// Holds the key of the field currently checked - empty if it's the outermost object
-    pub key: &'a str,
-    /// One of the 2 objects that should be checked
-    pub a: &'a Mapping,
-    /// One of the 2 objects that should be checked
-    pub b: &'a Mapping,
-    /// Holds relevant data for the current run, such as file names, and user configs
-    pub working_context: &'a WorkingContext,
+/// Holds the data required to run a difference check on YAML data
+pub type CheckingData<'a, T> = crate::core::diff_types::CheckingData<'a, T, Value>;
+
+impl Node for Value {
+    type Object = Mapping;
+
+    fn value_type(&self) -> ValueType {
+        match self {
+            Value::Null => ValueType::Null,
+            Value::Bool(_) => ValueType::Boolean,
+            Value::Number(_) => ValueType::Number,
+            Value::String(_) => ValueType::String,
+            Value::Sequence(_) => ValueType::Array,
+            Value::Mapping(_) => ValueType::Object,
+            Value::Tagged(tagged) => ValueType::Tagged(tagged.tag.to_string()),
+        }
+    }
+
+    fn as_object(&self) -> Option<&Mapping> {
+        self.as_mapping()
+    }
+
+    fn as_array(&self) -> Option<&[Value]> {
+        self.as_sequence().map(Vec::as_slice)
+    }
+
+    fn to_display_string(&self) -> String {
+        match self {
+            Value::Tagged(tagged) => {
+                format!("{} {}", tagged.tag, tagged.value.to_display_string())
+            }
+            // String values are escaped by default if to_string() is called on them, so if it is a string, we call as_str() first.
+            value => value
+                .as_str()
+                .map_or_else(|| Stringable::to_string(value), |v| v.to_owned()),
+        }
+    }
 }
 
-impl<'a, T: Diff> CheckingData<'a, T> {
-    pub fn new(
-        key: &'a str,
-        a: &'a Mapping,
-        b: &'a Mapping,
-        working_context: &'a WorkingContext,
-    ) -> CheckingData<'a, T> {
-        let diff_collection: DiffCollection<T> = DiffCollection::new();
-        CheckingData {
-            diffs: diff_collection,
-            key,
-            a,
-            b,
-            working_context,
+impl MergeNode for Value {
+    fn child_mut(&mut self, segment: &PathSegment) -> Option<&mut Value> {
+        match (self, segment) {
+            (Value::Mapping(mapping), PathSegment::Key(key)) => {
+                let mapping_key = find_key(mapping, key)?;
+                mapping.get_mut(&mapping_key)
+            }
+            (Value::Sequence(items), PathSegment::Index(index)) => items.get_mut(*index),
+            _ => None,
         }
     }
-}
\ No newline at end of file
+
+    fn copy_field(&mut self, key: &str, source: &Value) {
+        let Value::Mapping(mapping) = self else {
+            return;
+        };
+
+        // The key is taken from the source, so keys, that aren't strings keep their original form
+        let source_field = source.as_mapping().and_then(|source| {
+            let source_key = find_key(source, key)?;
+            let value = source.get(&source_key)?.clone();
+            Some((source_key, value))
+        });
+
+        match source_field {
+            Some((source_key, value)) => {
+                mapping.insert(source_key, value);
+            }
+            None => {
+                if let Some(mapping_key) = find_key(mapping, key) {
+                    mapping.shift_remove(&mapping_key);
+                }
+            }
+        }
+    }
+}
+
+/// Returns the key of the mapping, that is rendered as `key`
+fn find_key(mapping: &Mapping, key: &str) -> Option<Value> {
+    if let Some((mapping_key, _)) = find_non_string_key(mapping, key) {
+        return Some(mapping_key.clone());
+    }
+
+    let mapping_key = Value::String(unescape_string_key(mapping, key).to_owned());
+    mapping.contains_key(&mapping_key).then_some(mapping_key)
+}
+
+/// Mapping keys, that aren't strings, are rendered with their YAML tag, e.g. `!!int 1`, `!!bool true` or `!!seq ["a","b"]`.
+/// String keys are used as they are, like in every other format, so they match the keys of other formats.
+/// Only if a string key is rendered the same as a key of the mapping, that isn't a string, does it get a `!!str ` prefix to keep them apart.
+impl NodeObject<Value> for Mapping {
+    fn entries(&self) -> Box<dyn Iterator<Item = (Cow<'_, str>, &Value)> + '_> {
+        Box::new(self.iter().map(|(key, value)| match key {
+            Value::String(string) if find_non_string_key(self, string).is_some() => {
+                (Cow::from(format!("{}{}", STRING_TAG_PREFIX, string)), value)
+            }
+            key => (key_to_string(key), value),
+        }))
+    }
+
+    fn get(&self, key: &str) -> Option<&Value> {
+        match find_non_string_key(self, key) {
+            Some((_, value)) => Some(value),
+            None => Mapping::get(self, unescape_string_key(self, key)),
+        }
+    }
+}
+
+/// The prefix of string keys, that are rendered the same as a key of the mapping, that isn't a string
+const STRING_TAG_PREFIX: &str = "!!str ";
+
+/// Returns the entry of the mapping, whose key isn't a string and is rendered as `key`
+fn find_non_string_key<'a>(mapping: &'a Mapping, key: &str) -> Option<(&'a Value, &'a Value)> {
+    if !key.starts_with('!') {
+        return None;
+    }
+
+    mapping
+        .iter()
+        .find(|(mapping_key, _)| !mapping_key.is_string() && key_to_string(mapping_key) == key)
+}
+
+/// Returns the string key rendered as `key`, removing the `!!str ` prefix, if it was added to tell the key apart
+fn unescape_string_key<'a>(mapping: &Mapping, key: &'a str) -> &'a str {
+    match key.strip_prefix(STRING_TAG_PREFIX) {
+        Some(string_key) if find_non_string_key(mapping, string_key).is_some() => string_key,
+        _ => key,
+    }
+}
+
+fn key_to_string(key: &Value) -> Cow<'_, str> {
+    match key {
+        Value::String(string) => Cow::from(string.as_str()),
+        Value::Null => Cow::from("!!null null"),
+        Value::Bool(boolean) => Cow::from(format!("!!bool {}", boolean)),
+        Value::Number(number) if number.is_f64() => Cow::from(format!("!!float {}", number)),
+        Value::Number(number) => Cow::from(format!("!!int {}", number)),
+        Value::Sequence(_) => Cow::from(format!("!!seq {}", to_flow_string(key))),
+        Value::Mapping(_) => Cow::from(format!("!!map {}", to_flow_string(key))),
+        Value::Tagged(tagged) => {
+            Cow::from(format!("{} {}", tagged.tag, to_flow_string(&tagged.value)))
+        }
+    }
+}
+
+/// Renders a value on a single line, so it can be part of a key
+fn to_flow_string(value: &Value) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| Stringable::to_string(value).replace('\n', " "))
+}
+
+/// Describes how the documents of 2 yaml streams are paired up for comparison
+#[derive(Debug, Clone, PartialEq)]
+pub enum DocumentPairing {
+    /// The n-th document of one stream is compared to the n-th document of the other one
+    ByPosition,
+    /// Documents with the same values at the given paths are compared, e.g. `kind` and `metadata.name` for Kubernetes manifests.
+    /// Values of different types, like `1` and `"1"`, are different identities.
+    ByIdentity(Vec<Path>),
+}
+
+/// Options for reading in yaml data
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct YamlOptions {
+    /// Whether `<<` merge keys should be applied, so the data reflects the effective configuration instead of holding literal `<<` keys
+    pub merge_keys: bool,
+    /// The most values the aliases may add to the data, when they are expanded. Guards against alias bombs.
+    /// Values written out in the data itself don't count against it.
+    pub value_limit: usize,
+}
+
+impl YamlOptions {
+    pub fn new(merge_keys: bool, value_limit: usize) -> YamlOptions {
+        YamlOptions {
+            merge_keys,
+            value_limit,
+        }
+    }
+}
+
+impl Default for YamlOptions {
+    /// Merge keys are left as they are and the aliases may add up to 100 000 values
+    fn default() -> Self {
+        YamlOptions::new(false, 100_000)
+    }
+}
@@ -1,24 +1,935 @@
-use std::fs::File;
-use std::io::BufReader;
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+
+use serde::de::DeserializeSeed;
+use serde_yaml::{Deserializer, Mapping, Value};
+
+use crate::core::{
+    comparison::{self, Comparison},
+    diff_types::{ComparisionResult, DiffKinds, KeyDiff, MergeResult, WorkingContext},
+    error::{Error, Result},
+    node::{identity_at, Node},
+    path::Path,
+    record_object,
+};
+
+use self::{
+    diff_types::{DocumentPairing, YamlOptions},
+    value_limit::{LimitedValue, ValueBudget},
+};
 
-mod array_checker;
 pub mod diff_types;
-mod key_checker;
-mod type_checker;
-mod value_checker;
-
-pub fn read_yaml_file(file_path: &str) -> serde_yaml::Result<serde_yaml::Mapping> {
-    let file =
-        File::open(file_path).unwrap_or_else(|_| panic!("Could not open file {}", file_path));
-    let reader = BufReader::new(file);
-    let result = serde_yaml::from_reader(reader)?;
-    Ok(result)
-}
-
-fn format_key(key_in: &str, current_key: &str) -> String {
-    if key_in.is_empty() {
-        current_key.to_owned()
-    } else {
-        format!("{}.{}", key_in, current_key)
+mod value_limit;
+
+pub(crate) use self::value_limit::VALUE_LIMIT_EXCEEDED;
+
+/// Name used in the errors of data read from a reader
+const READER_NAME: &str = "YAML input";
+
+/// Reads in a yaml file
+///
+/// # Errors
+/// Returns an error if the file cannot be read, it isn't valid YAML or its outermost value isn't a mapping.
+pub fn read_yaml_file(file_path: &str) -> Result<Mapping> {
+    let content = fs::read(file_path).map_err(|error| Error::io(file_path, error))?;
+    read_yaml_slice(&content)
+}
+
+/// Reads in yaml data from a string
+///
+/// # Errors
+/// Returns an error if the content isn't valid YAML or its outermost value isn't a mapping.
+pub fn read_yaml_str(content: &str) -> Result<Mapping> {
+    read_yaml_str_with_options(content, &YamlOptions::default())
+}
+
+/// Reads in a yaml file with the given options, e.g. to apply merge keys
+///
+/// # Errors
+/// Returns an error if the file cannot be read, it isn't valid YAML, its aliases add more values than the limit or its outermost value isn't a mapping.
+pub fn read_yaml_file_with_options(file_path: &str, options: &YamlOptions) -> Result<Mapping> {
+    let content = fs::read(file_path).map_err(|error| Error::io(file_path, error))?;
+    mapping_from_value(read_value(
+        Deserializer::from_slice(&content),
+        &ValueBudget::new(content.len(), options.value_limit),
+        options,
+    )?)
+}
+
+/// Reads in yaml data from a string with the given options, e.g. to apply merge keys
+///
+/// # Errors
+/// Returns an error if the content isn't valid YAML, its aliases add more values than the limit or its outermost value isn't a mapping.
+pub fn read_yaml_str_with_options(content: &str, options: &YamlOptions) -> Result<Mapping> {
+    mapping_from_value(read_value(
+        Deserializer::from_str(content),
+        &ValueBudget::new(content.len(), options.value_limit),
+        options,
+    )?)
+}
+
+/// Reads in a yaml file, that may have any value as its outermost value, e.g. a sequence of fixtures
+///
+/// # Errors
+/// Returns an error if the file cannot be read or it isn't valid YAML.
+pub fn read_yaml_value_file(file_path: &str) -> Result<Value> {
+    let content = fs::read(file_path).map_err(|error| Error::io(file_path, error))?;
+    read_value_with_default_options(Deserializer::from_slice(&content), content.len())
+}
+
+/// Reads in yaml data, that may have any value as its outermost value, from a string
+///
+/// # Errors
+/// Returns an error if the content isn't valid YAML.
+pub fn read_yaml_value_str(content: &str) -> Result<Value> {
+    read_value_with_default_options(Deserializer::from_str(content), content.len())
+}
+
+/// Reads in yaml data from bytes
+///
+/// # Errors
+/// Returns an error if the content isn't valid YAML or its outermost value isn't a mapping.
+pub fn read_yaml_slice(content: &[u8]) -> Result<Mapping> {
+    mapping_from_value(read_value_with_default_options(
+        Deserializer::from_slice(content),
+        content.len(),
+    )?)
+}
+
+/// Reads in yaml data from any reader, like stdin or a network stream
+///
+/// # Errors
+/// Returns an error if the reader fails, the content isn't valid YAML or its outermost value isn't a mapping.
+pub fn read_yaml_reader<R: Read>(mut reader: R) -> Result<Mapping> {
+    let mut content = vec![];
+    reader
+        .read_to_end(&mut content)
+        .map_err(|error| Error::io(READER_NAME, error))?;
+    read_yaml_slice(&content)
+}
+
+/// Reads in every document of a yaml stream file, like a Kubernetes manifest bundle with `---` separators.
+/// Empty documents are skipped.
+///
+/// # Errors
+/// Returns an error if the file cannot be read, it isn't valid YAML or one of the documents isn't a mapping.
+pub fn read_yaml_documents_file(file_path: &str) -> Result<Vec<Mapping>> {
+    read_yaml_documents_file_with_options(file_path, &YamlOptions::default())
+}
+
+/// Reads in every document of a yaml stream from a string
+///
+/// # Errors
+/// Returns an error if the content isn't valid YAML or one of the documents isn't a mapping.
+pub fn read_yaml_documents_str(content: &str) -> Result<Vec<Mapping>> {
+    read_yaml_documents_str_with_options(content, &YamlOptions::default())
+}
+
+/// Reads in every document of a yaml stream file with the given options. The value limit applies to the aliases of each document on its own.
+///
+/// # Errors
+/// Returns an error if the file cannot be read, it isn't valid YAML, the aliases of a document add more values than the limit or one of the documents isn't a mapping.
+pub fn read_yaml_documents_file_with_options(
+    file_path: &str,
+    options: &YamlOptions,
+) -> Result<Vec<Mapping>> {
+    let content = fs::read(file_path).map_err(|error| Error::io(file_path, error))?;
+    read_documents(Deserializer::from_slice(&content), content.len(), options)
+}
+
+/// Reads in every document of a yaml stream from a string with the given options. The value limit applies to the aliases of each document on its own.
+///
+/// # Errors
+/// Returns an error if the content isn't valid YAML, the aliases of a document add more values than the limit or one of the documents isn't a mapping.
+pub fn read_yaml_documents_str_with_options(
+    content: &str,
+    options: &YamlOptions,
+) -> Result<Vec<Mapping>> {
+    read_documents(Deserializer::from_str(content), content.len(), options)
+}
+
+/// Reads in every document of a yaml stream from bytes
+///
+/// # Errors
+/// Returns an error if the content isn't valid YAML or one of the documents isn't a mapping.
+pub fn read_yaml_documents_slice(content: &[u8]) -> Result<Vec<Mapping>> {
+    read_documents(
+        Deserializer::from_slice(content),
+        content.len(),
+        &YamlOptions::default(),
+    )
+}
+
+/// Reads in every document of a yaml stream from any reader, like stdin or a network stream
+///
+/// # Errors
+/// Returns an error if the reader fails, the content isn't valid YAML or one of the documents isn't a mapping.
+pub fn read_yaml_documents_reader<R: Read>(mut reader: R) -> Result<Vec<Mapping>> {
+    let mut content = vec![];
+    reader
+        .read_to_end(&mut content)
+        .map_err(|error| Error::io(READER_NAME, error))?;
+    read_yaml_documents_slice(&content)
+}
+
+/// Applies the `<<` merge keys of the mapping and its nested mappings, so it holds the effective configuration.
+/// Keys set in a mapping take precedence over the merged ones, as described in <https://yaml.org/type/merge.html>.
+///
+/// # Errors
+/// Returns an error if a merge key's value isn't a mapping or a sequence of mappings.
+pub fn apply_merge_keys(mapping: &mut Mapping) -> Result<()> {
+    let mut value = Value::Mapping(std::mem::take(mapping));
+    let result = value.apply_merge().map_err(Error::from_yaml);
+
+    if let Value::Mapping(merged) = value {
+        *mapping = merged;
+    }
+    result
+}
+
+/// Runs the difference checks selected in `diff_kinds` on `a` against `b` in a single traversal
+pub fn compare(
+    a: &Mapping,
+    b: &Mapping,
+    working_context: &WorkingContext,
+    diff_kinds: DiffKinds,
+) -> ComparisionResult {
+    comparison::compare::<Value, Value>(a, b, working_context, diff_kinds)
+}
+
+/// Runs the difference checks selected in `diff_kinds` on 2 values of any type, e.g. top-level sequences, in a single traversal.
+/// The differences of the outermost values themselves have `ROOT_KEY` as their key.
+pub fn compare_values(
+    a: &Value,
+    b: &Value,
+    working_context: &WorkingContext,
+    diff_kinds: DiffKinds,
+) -> ComparisionResult {
+    comparison::compare_values(a, b, working_context, diff_kinds)
+}
+
+/// Merges the changes `ours` and `theirs` made to `base`, e.g. a customized config and a new default one.
+/// Changes of only one side are merged automatically. If both sides changed the same field differently, it keeps the value of `ours` and the conflict is returned as a `ConflictDiff`.
+pub fn merge(
+    base: &Value,
+    ours: &Value,
+    theirs: &Value,
+    working_context: &WorkingContext,
+) -> MergeResult<Value> {
+    crate::core::merge::merge(base, ours, theirs, working_context)
+}
+
+/// Runs the difference checks selected in `diff_kinds` on the documents of 2 yaml streams, paired up as set in `pairing`.
+/// The paths of the differences start with the index or identity of the document, e.g. `documents[0].spec.replicas` or `documents[kind="Deployment",metadata.name="web"].spec.replicas`.
+/// The identity values are rendered as JSON, so `1` and `"1"` are different identities. A document, that lacks one of the identity values is keyed by its index instead.
+/// A document, that has no pair in the other stream is a `KeyDiff` with the path of the document, recording the document if the config asks for it.
+/// If several documents share an identity, they are paired in their order and the repeated identities get a `#2`, `#3`, ... suffix.
+pub fn compare_streams(
+    a: &[Mapping],
+    b: &[Mapping],
+    working_context: &WorkingContext,
+    pairing: &DocumentPairing,
+    diff_kinds: DiffKinds,
+) -> ComparisionResult {
+    let a_documents = document_keys(a, pairing);
+    let b_documents = document_keys(b, pairing);
+    let a_index: HashMap<&Path, &Mapping> = a_documents
+        .iter()
+        .map(|(key, document)| (key, *document))
+        .collect();
+    let b_index: HashMap<&Path, &Mapping> = b_documents
+        .iter()
+        .map(|(key, document)| (key, *document))
+        .collect();
+
+    let mut comparison = Comparison::new(working_context, diff_kinds);
+    let mut missing_documents = vec![];
+
+    let config = &working_context.config;
+    for (key, a_document) in a_documents.iter().filter(|(key, _)| !config.skips(key)) {
+        match b_index.get(key) {
+            Some(b_document) => {
+                comparison.compare_objects::<Value, Value>(key, a_document, b_document)
+            }
+            None if !config.reports_missing_object::<Value>(key, a_document) => {}
+            None => missing_documents.push(
+                KeyDiff::new(
+                    key.clone(),
+                    working_context.file_a.name.clone(),
+                    working_context.file_b.name.clone(),
+                )
+                .with_json(record_object::<Value>(config, a_document)),
+            ),
+        }
+    }
+
+    for (key, b_document) in &b_documents {
+        if !a_index.contains_key(key) && config.reports_missing_object::<Value>(key, b_document) {
+            missing_documents.push(
+                KeyDiff::new(
+                    key.clone(),
+                    working_context.file_b.name.clone(),
+                    working_context.file_a.name.clone(),
+                )
+                .with_json(record_object::<Value>(config, b_document)),
+            );
+        }
+    }
+
+    let (mut key_diffs, type_diffs, value_diffs, array_diffs) = comparison.into_result();
+    if diff_kinds.keys {
+        key_diffs.append(&mut missing_documents);
+    }
+    (key_diffs, type_diffs, value_diffs, array_diffs)
+}
+
+/// Returns the mapping, if it is the outermost value of the data set
+pub(crate) fn mapping_from_value(value: Value) -> Result<Mapping> {
+    match value {
+        Value::Mapping(mapping) => Ok(mapping),
+        other => Err(Error::UnsupportedRoot {
+            format: "YAML",
+            found: other.value_type(),
+        }),
+    }
+}
+
+/// Reads in a single value, counting the values the aliases add against the budget, then applies the merge keys if needed
+fn read_value(
+    deserializer: Deserializer,
+    budget: &ValueBudget,
+    options: &YamlOptions,
+) -> Result<Value> {
+    let mut value = LimitedValue::new(budget)
+        .deserialize(deserializer)
+        .map_err(Error::from_yaml)?;
+
+    if options.merge_keys {
+        value.apply_merge().map_err(Error::from_yaml)?;
+    }
+
+    Ok(value)
+}
+
+fn read_value_with_default_options(deserializer: Deserializer, length: usize) -> Result<Value> {
+    let options = YamlOptions::default();
+    read_value(
+        deserializer,
+        &ValueBudget::new(length, options.value_limit),
+        &options,
+    )
+}
+
+/// Reads in every document of the stream. The aliases of each document may add up to the limit of the options.
+fn read_documents(
+    deserializer: Deserializer,
+    length: usize,
+    options: &YamlOptions,
+) -> Result<Vec<Mapping>> {
+    let mut documents = vec![];
+    let budget = ValueBudget::new(length, options.value_limit);
+
+    for document in deserializer {
+        budget.next_document();
+
+        match read_value(document, &budget, options)? {
+            Value::Null => {}
+            value => documents.push(mapping_from_value(value)?),
+        }
+    }
+
+    Ok(documents)
+}
+
+/// Creates the path prefix of each document, that is used for pairing them up.
+/// A document without one of the identity values is keyed by its index, so it can't be mistaken for a document with a `null` value.
+fn document_keys<'a>(
+    documents: &'a [Mapping],
+    pairing: &DocumentPairing,
+) -> Vec<(Path, &'a Mapping)> {
+    let mut occurrences: HashMap<String, usize> = HashMap::new();
+    let documents_path = Path::root().with_key("documents");
+
+    documents
+        .iter()
+        .enumerate()
+        .map(|(index, document)| {
+            let identity = match pairing {
+                DocumentPairing::ByPosition => None,
+                DocumentPairing::ByIdentity(paths) => paths
+                    .iter()
+                    .map(|path| {
+                        identity_at::<Value>(document, path)
+                            .map(|value| format!("{}={}", path, value))
+                    })
+                    .collect::<Option<Vec<String>>>()
+                    .map(|values| values.join(",")),
+            };
+
+            let Some(identity) = identity else {
+                return (documents_path.with_index(index), document);
+            };
+
+            let occurrence = occurrences.entry(identity.clone()).or_insert(0);
+            *occurrence += 1;
+
+            let key = match *occurrence {
+                1 => documents_path.with_selector(&identity),
+                _ => documents_path.with_selector(&format!("{}#{}", identity, occurrence)),
+            };
+            (key, document)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::core::{
+        diff_types::{
+            ArrayDiff, ArrayDiffDesc, Config, ConflictDiff, DiffKinds, KeyDiff, TypeDiff,
+            ValueDiff, WorkingContext, WorkingFile,
+        },
+        error::Error,
+        path::Path,
+    };
+
+    use super::{
+        apply_merge_keys, compare_streams,
+        diff_types::{DocumentPairing, YamlOptions},
+        merge, read_yaml_documents_str, read_yaml_documents_str_with_options, read_yaml_reader,
+        read_yaml_slice, read_yaml_str, read_yaml_str_with_options, read_yaml_value_str,
+    };
+
+    const FILE_NAME_A: &str = "a.yaml";
+    const FILE_NAME_B: &str = "b.yaml";
+
+    const CONTENT: &str = "a:\n  b:\n    - 1\n    - 2\n";
+
+    #[test]
+    fn test_read_yaml_str() {
+        let result = read_yaml_str(CONTENT).unwrap();
+
+        assert_eq!(1, result.len());
+        assert!(result["a"]["b"].is_sequence());
+    }
+
+    #[test]
+    fn test_read_yaml_slice() {
+        let result = read_yaml_slice(CONTENT.as_bytes()).unwrap();
+
+        assert_eq!(read_yaml_str(CONTENT).unwrap(), result);
+    }
+
+    #[test]
+    fn test_read_yaml_reader() {
+        let result = read_yaml_reader(CONTENT.as_bytes()).unwrap();
+
+        assert_eq!(read_yaml_str(CONTENT).unwrap(), result);
+    }
+
+    #[test]
+    fn test_read_yaml_documents_str() {
+        // act
+        let result = read_yaml_documents_str("a: 1\n---\n---\nb: 2\n...\n").unwrap();
+
+        // assert
+        assert_eq!(2, result.len());
+        assert_eq!(1, result[0]["a"].as_i64().unwrap());
+        assert_eq!(2, result[1]["b"].as_i64().unwrap());
+        assert!(matches!(
+            read_yaml_documents_str("a: 1\n---\n- 1\n"),
+            Err(Error::UnsupportedRoot { .. })
+        ));
+    }
+
+    #[test]
+    fn test_compare_streams_by_position() {
+        // arrange
+        let a = read_yaml_documents_str("a: 1\n---\nb: 1\n").unwrap();
+        let b = read_yaml_documents_str("a: 2\n").unwrap();
+
+        // act
+        let (key_diffs, type_diffs, value_diffs, array_diffs) = compare_streams(
+            &a,
+            &b,
+            &create_test_working_context(),
+            &DocumentPairing::ByPosition,
+            DiffKinds::all(),
+        );
+
+        // assert
+        assert_eq!(
+            vec![KeyDiff::new(
+                "documents[1]".to_owned(),
+                FILE_NAME_A.to_owned(),
+                FILE_NAME_B.to_owned()
+            )],
+            key_diffs
+        );
+        assert!(type_diffs.is_empty());
+        assert_eq!(
+            vec![ValueDiff::new(
+                "documents[0].a".to_owned(),
+                "1".to_owned(),
+                "2".to_owned()
+            )],
+            value_diffs
+        );
+        assert!(array_diffs.is_empty());
+    }
+
+    #[test]
+    fn test_compare_streams_by_identity() {
+        // arrange
+        let a = read_yaml_documents_str(
+            "kind: Service\nmetadata:\n  name: web\n---\n\
+             kind: Deployment\nmetadata:\n  name: web\nspec:\n  replicas: 1\n",
+        )
+        .unwrap();
+        let b = read_yaml_documents_str(
+            "kind: Deployment\nmetadata:\n  name: web\nspec:\n  replicas: 3\n---\n\
+             kind: ConfigMap\nmetadata:\n  name: web\n",
+        )
+        .unwrap();
+        let pairing = DocumentPairing::ByIdentity(vec!["kind".into(), "metadata.name".into()]);
+
+        // act
+        let (key_diffs, _, value_diffs, _) = compare_streams(
+            &a,
+            &b,
+            &create_test_working_context(),
+            &pairing,
+            DiffKinds::all(),
+        );
+
+        // assert
+        assert_eq!(
+            vec![
+                KeyDiff::new(
+                    "documents[kind=\"Service\",metadata.name=\"web\"]".to_owned(),
+                    FILE_NAME_A.to_owned(),
+                    FILE_NAME_B.to_owned()
+                ),
+                KeyDiff::new(
+                    "documents[kind=\"ConfigMap\",metadata.name=\"web\"]".to_owned(),
+                    FILE_NAME_B.to_owned(),
+                    FILE_NAME_A.to_owned()
+                ),
+            ],
+            key_diffs
+        );
+        assert_eq!(
+            vec![ValueDiff::new(
+                "documents[kind=\"Deployment\",metadata.name=\"web\"].spec.replicas".to_owned(),
+                "1".to_owned(),
+                "3".to_owned()
+            )],
+            value_diffs
+        );
+    }
+
+    #[test]
+    fn test_compare_streams_by_typed_identity() {
+        // arrange
+        let a = read_yaml_documents_str("id: 1\n---\nv: 1\n").unwrap();
+        let b = read_yaml_documents_str("id: \"1\"\n---\nid: null\nv: 1\n").unwrap();
+
+        // act
+        let (key_diffs, type_diffs, value_diffs, _) = compare_streams(
+            &a,
+            &b,
+            &create_test_working_context(),
+            &DocumentPairing::ByIdentity(vec!["id".into()]),
+            DiffKinds::all(),
+        );
+
+        // assert
+        assert_eq!(
+            vec![
+                KeyDiff::new(
+                    "documents[id=1]".to_owned(),
+                    FILE_NAME_A.to_owned(),
+                    FILE_NAME_B.to_owned()
+                ),
+                KeyDiff::new(
+                    "documents[1]".to_owned(),
+                    FILE_NAME_A.to_owned(),
+                    FILE_NAME_B.to_owned()
+                ),
+                KeyDiff::new(
+                    "documents[id=\"1\"]".to_owned(),
+                    FILE_NAME_B.to_owned(),
+                    FILE_NAME_A.to_owned()
+                ),
+                KeyDiff::new(
+                    "documents[id=null]".to_owned(),
+                    FILE_NAME_B.to_owned(),
+                    FILE_NAME_A.to_owned()
+                ),
+            ],
+            key_diffs
+        );
+        assert!(type_diffs.is_empty());
+        assert!(value_diffs.is_empty());
+    }
+
+    #[test]
+    fn test_compare_streams_records_documents() {
+        // arrange
+        let a = read_yaml_documents_str("kind: Service\nport: 80\n").unwrap();
+        let b = read_yaml_documents_str("kind: Deployment\nreplicas: 2\n").unwrap();
+        let mut working_context = create_test_working_context();
+        working_context.config.record_values = true;
+
+        // act
+        let (key_diffs, _, _, _) = compare_streams(
+            &a,
+            &b,
+            &working_context,
+            &DocumentPairing::ByIdentity(vec!["kind".into()]),
+            DiffKinds::all(),
+        );
+
+        // assert
+        assert_eq!(
+            vec![
+                KeyDiff::new(
+                    "documents[kind=\"Service\"]".to_owned(),
+                    FILE_NAME_A.to_owned(),
+                    FILE_NAME_B.to_owned()
+                )
+                .with_json(Some(json!({ "kind": "Service", "port": 80 }))),
+                KeyDiff::new(
+                    "documents[kind=\"Deployment\"]".to_owned(),
+                    FILE_NAME_B.to_owned(),
+                    FILE_NAME_A.to_owned()
+                )
+                .with_json(Some(json!({ "kind": "Deployment", "replicas": 2 }))),
+            ],
+            key_diffs
+        );
+    }
+
+    #[test]
+    fn test_compare_streams_by_identity_with_dotted_key() {
+        // arrange
+        let a = read_yaml_documents_str("labels:\n  app.kubernetes.io/name: web\nv: 1\n").unwrap();
+        let b = read_yaml_documents_str("labels:\n  app.kubernetes.io/name: web\nv: 2\n").unwrap();
+        let identity = Path::root()
+            .with_key("labels")
+            .with_key("app.kubernetes.io/name");
+
+        // act
+        let (_, _, value_diffs, _) = compare_streams(
+            &a,
+            &b,
+            &create_test_working_context(),
+            &DocumentPairing::ByIdentity(vec![identity.clone()]),
+            DiffKinds::all(),
+        );
+
+        // assert
+        assert_eq!(
+            vec![ValueDiff::new(
+                Path::root()
+                    .with_key("documents")
+                    .with_selector(&format!("{}=\"web\"", identity))
+                    .with_key("v"),
+                "1".to_owned(),
+                "2".to_owned()
+            )],
+            value_diffs
+        );
+    }
+
+    #[test]
+    fn test_compare_non_string_keys() {
+        // arrange
+        let a = read_yaml_str(
+            "1: int\n\"1\": string\ntrue: bool\n~: null\n? [a, b]\n: seq\n\"!x\": bang\nonly_a: 1\n",
+        )
+        .unwrap();
+        let b = read_yaml_str(
+            "1: other\n\"1\": string\n\"true\": string\n~: null\n? [a, b]\n: seq\n\"!x\": bang\n",
+        )
+        .unwrap();
+
+        // act
+        let (key_diffs, _, value_diffs, _) =
+            super::compare(&a, &b, &create_test_working_context(), DiffKinds::all());
+
+        // assert
+        assert_eq!(
+            vec![
+                KeyDiff::new(
+                    "!!bool true".to_owned(),
+                    FILE_NAME_A.to_owned(),
+                    FILE_NAME_B.to_owned()
+                ),
+                KeyDiff::new(
+                    "only_a".to_owned(),
+                    FILE_NAME_A.to_owned(),
+                    FILE_NAME_B.to_owned()
+                ),
+                KeyDiff::new(
+                    "true".to_owned(),
+                    FILE_NAME_B.to_owned(),
+                    FILE_NAME_A.to_owned()
+                ),
+            ],
+            key_diffs
+        );
+        assert_eq!(
+            vec![ValueDiff::new(
+                "!!int 1".to_owned(),
+                "int".to_owned(),
+                "other".to_owned()
+            )],
+            value_diffs
+        );
+    }
+
+    #[test]
+    fn test_compare_string_key_rendered_as_non_string_key() {
+        // arrange
+        let a = read_yaml_str("1: int\n\"!!int 1\": a\n").unwrap();
+        let b = read_yaml_str("1: int\n\"!!int 1\": b\n").unwrap();
+
+        // act
+        let (key_diffs, _, value_diffs, _) =
+            super::compare(&a, &b, &create_test_working_context(), DiffKinds::all());
+
+        // assert
+        assert!(key_diffs.is_empty());
+        assert_eq!(
+            vec![ValueDiff::new(
+                "!!str !!int 1".to_owned(),
+                "a".to_owned(),
+                "b".to_owned()
+            )],
+            value_diffs
+        );
+    }
+
+    #[test]
+    fn test_read_yaml_str_with_options_merge_keys() {
+        // arrange
+        let inherited = "defaults: &defaults\n  image: rust\n  retries: 2\nbuild:\n  <<: *defaults\n  retries: 3\n";
+        let inlined =
+            "defaults:\n  image: rust\n  retries: 2\nbuild:\n  image: rust\n  retries: 3\n";
+
+        // act
+        let a = read_yaml_str_with_options(inherited, &YamlOptions::new(true, 100)).unwrap();
+        let b = read_yaml_str(inlined).unwrap();
+        let (key_diffs, type_diffs, value_diffs, array_diffs) =
+            super::compare(&a, &b, &create_test_working_context(), DiffKinds::all());
+
+        // assert
+        assert!(key_diffs.is_empty());
+        assert!(type_diffs.is_empty());
+        assert!(value_diffs.is_empty());
+        assert!(array_diffs.is_empty());
+    }
+
+    #[test]
+    fn test_apply_merge_keys() {
+        // arrange
+        let mut mapping = read_yaml_str("base: &base { a: 1 }\nchild:\n  <<: *base\n").unwrap();
+        let mut invalid = read_yaml_str("child:\n  <<: 1\n").unwrap();
+
+        // act
+        let result = apply_merge_keys(&mut mapping);
+
+        // assert
+        assert!(result.is_ok());
+        assert_eq!(1, mapping["child"]["a"].as_i64().unwrap());
+        assert!(mapping["child"].get("<<").is_none());
+        assert!(matches!(
+            apply_merge_keys(&mut invalid),
+            Err(Error::Parse { .. })
+        ));
+    }
+
+    #[test]
+    fn test_read_yaml_str_with_options_value_limit() {
+        // arrange
+        let bomb = "a: &a [x, x, x, x, x, x, x, x, x, x]\n\
+                    b: &b [*a, *a, *a, *a, *a, *a, *a, *a, *a, *a]\n\
+                    c: &c [*b, *b, *b, *b, *b, *b, *b, *b, *b, *b]\n\
+                    d: [*c, *c, *c, *c, *c, *c, *c, *c, *c, *c]\n";
+
+        // act
+        let result = read_yaml_str_with_options(bomb, &YamlOptions::new(false, 1000));
+
+        // assert
+        assert!(matches!(result, Err(Error::LimitExceeded { .. })));
+        assert!(read_yaml_str_with_options(bomb, &YamlOptions::new(false, 20_000)).is_ok());
+        assert!(matches!(
+            read_yaml_str(&format!(
+                "{}e: [{}]\n",
+                bomb.replace("d: [", "d: &d ["),
+                ["*d"; 10].join(", ")
+            )),
+            Err(Error::LimitExceeded { .. })
+        ));
+    }
+
+    #[test]
+    fn test_read_yaml_str_large_without_aliases() {
+        // arrange
+        let content: String = (0..60_000)
+            .map(|index| format!("key{}: value {}\n", index, index))
+            .collect();
+
+        // act
+        let result = read_yaml_str(&content).unwrap();
+
+        // assert
+        assert_eq!(60_000, result.len());
+        assert!(read_yaml_slice(content.as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn test_read_yaml_documents_str_with_options() {
+        // arrange
+        let bomb = "a: &a [x, x, x, x, x, x, x, x, x, x]\n\
+                    b: &b [*a, *a, *a, *a, *a, *a, *a, *a, *a, *a]\n\
+                    c: [*b, *b, *b, *b, *b, *b, *b, *b, *b, *b]\n";
+        let content = format!(
+            "base: &base {{ a: 1 }}\nchild:\n  <<: *base\n---\n{}---\n{}",
+            bomb, bomb
+        );
+
+        // act
+        let result =
+            read_yaml_documents_str_with_options(&content, &YamlOptions::new(true, 1500)).unwrap();
+
+        // assert
+        assert_eq!(3, result.len());
+        assert_eq!(1, result[0]["child"]["a"].as_i64().unwrap());
+        assert!(matches!(
+            read_yaml_documents_str_with_options(&content, &YamlOptions::new(true, 500)),
+            Err(Error::LimitExceeded { .. })
+        ));
+    }
+
+    #[test]
+    fn test_compare_tags() {
+        // arrange
+        let a = read_yaml_str(
+            "ref: !Ref Foo\nsame: !Ref Bar\nuntagged: Baz\nsub: !Sub { a: 1 }\nitems: [!Ref A, !Ref B]\n",
+        )
+        .unwrap();
+        let b = read_yaml_str(
+            "ref: !GetAtt Foo.Arn\nsame: !Ref Bar\nuntagged: !Ref Baz\nsub: !Sub { a: 2 }\nitems: [!Ref A, B]\n",
+        )
+        .unwrap();
+
+        // act
+        let (key_diffs, type_diffs, value_diffs, array_diffs) =
+            super::compare(&a, &b, &create_test_working_context(), DiffKinds::all());
+
+        // assert
+        assert!(key_diffs.is_empty());
+        assert_eq!(
+            vec![
+                TypeDiff::new("ref".to_owned(), "!Ref".to_owned(), "!GetAtt".to_owned()),
+                TypeDiff::new(
+                    "untagged".to_owned(),
+                    "string".to_owned(),
+                    "!Ref".to_owned()
+                ),
+            ],
+            type_diffs
+        );
+        assert_eq!(
+            vec![
+                ValueDiff::new(
+                    "ref".to_owned(),
+                    "!Ref Foo".to_owned(),
+                    "!GetAtt Foo.Arn".to_owned()
+                ),
+                ValueDiff::new(
+                    "untagged".to_owned(),
+                    "Baz".to_owned(),
+                    "!Ref Baz".to_owned()
+                ),
+                ValueDiff::new("sub.a".to_owned(), "1".to_owned(), "2".to_owned()),
+                ValueDiff::new(
+                    "items".to_owned(),
+                    "Array differences present".to_owned(),
+                    "Array differences present".to_owned()
+                ),
+            ],
+            value_diffs
+        );
+        assert_eq!(
+            vec![
+                ArrayDiff::new("items".to_owned(), ArrayDiffDesc::AHas, "!Ref B".to_owned()),
+                ArrayDiff::new("items".to_owned(), ArrayDiffDesc::AMisses, "B".to_owned()),
+                ArrayDiff::new("items".to_owned(), ArrayDiffDesc::BHas, "B".to_owned()),
+                ArrayDiff::new(
+                    "items".to_owned(),
+                    ArrayDiffDesc::BMisses,
+                    "!Ref B".to_owned()
+                ),
+            ],
+            array_diffs
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        // arrange
+        let base = read_yaml_value_str(
+            "name: web\nbucket: !Ref Bucket\n1: one\nports: [80]\nreplicas: 1\n",
+        )
+        .unwrap();
+        let ours = read_yaml_value_str(
+            "name: web\nbucket: !Ref Bucket\n1: uno\nports: [80]\nreplicas: 3\nextra: true\n",
+        )
+        .unwrap();
+        let theirs = read_yaml_value_str(
+            "name: api\nbucket: !GetAtt Bucket.Arn\n1: one\nports: [80, 443]\nreplicas: 2\n",
+        )
+        .unwrap();
+
+        // act
+        let result = merge(&base, &ours, &theirs, &create_test_working_context());
+
+        // assert
+        assert_eq!(
+            read_yaml_value_str(
+                "name: api\nbucket: !GetAtt Bucket.Arn\n1: uno\nports: [80, 443]\nreplicas: 3\nextra: true\n"
+            )
+            .unwrap(),
+            result.merged
+        );
+        assert_eq!(
+            "name: api\nbucket: !GetAtt Bucket.Arn\n1: uno\nports:\n- 80\n- 443\nreplicas: 3\nextra: true\n",
+            serde_yaml::to_string(&result.merged).unwrap()
+        );
+        assert_eq!(
+            vec![ConflictDiff::new(
+                "replicas".to_owned(),
+                Some("1".to_owned()),
+                Some("3".to_owned()),
+                Some("2".to_owned())
+            )],
+            result.conflicts
+        );
+    }
+
+    // Test utils
+
+    fn create_test_working_context() -> WorkingContext {
+        WorkingContext::new(
+            WorkingFile::new(FILE_NAME_A.to_owned()),
+            WorkingFile::new(FILE_NAME_B.to_owned()),
+            Config::new(false),
+        )
     }
 }
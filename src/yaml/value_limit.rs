@@ -0,0 +1,188 @@
+use std::{cell::Cell, fmt};
+
+use serde::de::{
+    self, DeserializeSeed, Deserializer, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor,
+};
+use serde_yaml::{
+    value::{Tag, TaggedValue},
+    Mapping, Value,
+};
+
+/// The start of the error message, when the limit is hit
+pub(crate) const VALUE_LIMIT_EXCEEDED: &str = "value limit exceeded";
+
+/// Counts the values read in. Every byte of the data may spell out a value of its own,
+/// anything beyond that was added by expanding an alias and is counted against the limit.
+pub(crate) struct ValueBudget {
+    written: Cell<usize>,
+    added: Cell<usize>,
+    limit: usize,
+}
+
+impl ValueBudget {
+    /// Creates the budget of data with the given length, whose aliases may add up to `limit` values
+    pub(crate) fn new(length: usize, limit: usize) -> ValueBudget {
+        ValueBudget {
+            written: Cell::new(length + 1),
+            added: Cell::new(limit),
+            limit,
+        }
+    }
+
+    /// Lets the next document of a stream add up to `limit` values again
+    pub(crate) fn next_document(&self) {
+        self.added.set(self.limit);
+    }
+
+    fn count<E: de::Error>(&self) -> Result<(), E> {
+        if let Some(written) = self.written.get().checked_sub(1) {
+            self.written.set(written);
+            return Ok(());
+        }
+
+        match self.added.get().checked_sub(1) {
+            Some(added) => {
+                self.added.set(added);
+                Ok(())
+            }
+            None => Err(E::custom(format!(
+                "{}: the aliases add more than {} values to the data",
+                VALUE_LIMIT_EXCEEDED, self.limit
+            ))),
+        }
+    }
+}
+
+/// Reads in a yaml value, like `serde_yaml::Value`'s own `Deserialize` implementation, but fails as soon as the aliases add more values than the budget allows.
+/// Aliases are expanded while reading, so this stops alias bombs before they use up the memory.
+pub(crate) struct LimitedValue<'a> {
+    budget: &'a ValueBudget,
+}
+
+impl<'a> LimitedValue<'a> {
+    pub(crate) fn new(budget: &'a ValueBudget) -> LimitedValue<'a> {
+        LimitedValue { budget }
+    }
+
+    fn count<E: de::Error>(&self) -> Result<(), E> {
+        self.budget.count()
+    }
+
+    fn child(&self) -> LimitedValue<'a> {
+        LimitedValue::new(self.budget)
+    }
+}
+
+impl<'de, 'a> DeserializeSeed<'de> for LimitedValue<'a> {
+    type Value = Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de, 'a> Visitor<'de> for LimitedValue<'a> {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any YAML value")
+    }
+
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<Value, E> {
+        self.count()?;
+        Ok(Value::Bool(value))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Value, E> {
+        self.count()?;
+        Ok(Value::Number(value.into()))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Value, E> {
+        self.count()?;
+        Ok(Value::Number(value.into()))
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Value, E> {
+        self.count()?;
+        Ok(Value::Number(value.into()))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Value, E> {
+        self.count()?;
+        Ok(Value::String(value.to_owned()))
+    }
+
+    fn visit_string<E: de::Error>(self, value: String) -> Result<Value, E> {
+        self.count()?;
+        Ok(Value::String(value))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Value, E> {
+        self.count()?;
+        Ok(Value::Null)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Value, E> {
+        self.count()?;
+        Ok(Value::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        self.deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut data: A) -> Result<Value, A::Error> {
+        self.count()?;
+        let mut sequence = vec![];
+
+        while let Some(item) = data.next_element_seed(self.child())? {
+            sequence.push(item);
+        }
+
+        Ok(Value::Sequence(sequence))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut data: A) -> Result<Value, A::Error> {
+        self.count()?;
+        let mut mapping = Mapping::new();
+
+        while let Some(key) = data.next_key_seed(self.child())? {
+            if mapping.contains_key(&key) {
+                return Err(de::Error::custom(format!(
+                    "duplicate entry with key {}",
+                    serde_json::to_string(&key).unwrap_or_default()
+                )));
+            }
+
+            let value = data.next_value_seed(self.child())?;
+            mapping.insert(key, value);
+        }
+
+        Ok(Value::Mapping(mapping))
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Value, A::Error> {
+        self.count()?;
+        let (tag, contents) = data.variant_seed(TagSeed)?;
+        let value = contents.newtype_variant_seed(self.child())?;
+        Ok(Value::Tagged(Box::new(TaggedValue { tag, value })))
+    }
+}
+
+/// Reads in the tag of a tagged value
+struct TagSeed;
+
+impl<'de> DeserializeSeed<'de> for TagSeed {
+    type Value = Tag;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Tag, D::Error> {
+        let tag: String = de::Deserialize::deserialize(deserializer)?;
+
+        if tag.is_empty() {
+            return Err(de::Error::custom("empty YAML tag is not allowed"));
+        }
+
+        Ok(Tag::new(tag))
+    }
+}